@@ -100,6 +100,103 @@ fn criterion_benchmark(criterion: &mut Criterion) {
     }
 
     alloc_bench.finish();
+
+    let mut contended_bench = criterion.benchmark_group("Contended");
+
+    for threads in [32, 64] {
+        contended_bench.bench_with_input(
+            BenchmarkId::new("Naive", threads),
+            &threads,
+            |b, threads| {
+                use arena64::arena::Arena64;
+
+                b.iter(|| {
+                    let arena: Arena64<usize> = Arena64::new();
+
+                    std::thread::scope(|scope| {
+                        for _ in 0..*threads {
+                            scope.spawn(|| {
+                                for i in 0..64 {
+                                    black_box(arena.insert(i));
+                                }
+                            });
+                        }
+                    });
+                });
+            },
+        );
+
+        contended_bench.bench_with_input(
+            BenchmarkId::new("Backoff", threads),
+            &threads,
+            |b, threads| {
+                use arena64::arena::Arena64;
+                use crossbeam_utils::Backoff;
+
+                b.iter(|| {
+                    let arena: Arena64<usize> = Arena64::new();
+
+                    std::thread::scope(|scope| {
+                        for _ in 0..*threads {
+                            scope.spawn(|| {
+                                for i in 0..64 {
+                                    black_box(arena.insert_with_backoff(i, &mut Backoff::new()));
+                                }
+                            });
+                        }
+                    });
+                });
+            },
+        );
+
+        contended_bench.bench_with_input(
+            BenchmarkId::new("SpinBackoff", threads),
+            &threads,
+            |b, threads| {
+                use arena64::{arena::Arena64, backoff::SpinBackoff};
+
+                b.iter(|| {
+                    let arena: Arena64<usize> = Arena64::new();
+
+                    std::thread::scope(|scope| {
+                        for _ in 0..*threads {
+                            scope.spawn(|| {
+                                for i in 0..64 {
+                                    black_box(
+                                        arena.insert_with_backoff(i, &mut SpinBackoff::new()),
+                                    );
+                                }
+                            });
+                        }
+                    });
+                });
+            },
+        );
+    }
+
+    contended_bench.finish();
+
+    criterion.bench_function("Deref/PointerChase", |bencher| {
+        use arena64::arena::Arena64;
+
+        let arena: Arena64<usize> = Arena64::new();
+
+        // Each value is the position of the next slot to visit, in a stride that
+        // visits every slot before returning to the start
+        let slots: Vec<_> = (0..4096)
+            .map(|i| arena.insert((i * 61 + 1) % 4096))
+            .collect();
+
+        bencher.iter(|| {
+            let mut position = 0;
+
+            for _ in 0..4096 {
+                position = *slots[position];
+            }
+
+            black_box(position)
+        });
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -1,14 +1,37 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use core::{
-    mem::MaybeUninit,
+    error::Error,
+    fmt::{self, Debug, Display},
+    mem::{self, MaybeUninit},
     ptr::{self},
     sync::atomic::{AtomicPtr, Ordering},
 };
+#[cfg(feature = "std")]
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+};
 
 use crossbeam_utils::atomic::AtomicConsume;
 
-use crate::boxed::Inner;
 pub use crate::boxed::Slot;
+use crate::{
+    backoff::{Backoff, NoBackoff},
+    boxed::Inner,
+};
+/// The allocator failed to provide memory for a new slab
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocError;
+
+impl Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+impl Error for AllocError {}
+
 /// A concurrent arena
 pub struct Arena64<T> {
     inner: AtomicPtr<Inner<T>>,
@@ -28,20 +51,14 @@ impl<T> Arena64<T> {
     }
 
     #[inline]
-    fn replace_inner(&self, current: *mut Inner<T>) -> *mut Inner<T> {
-        let inner: Box<Inner<T>> = unsafe { Box::new_uninit().assume_init() };
-        let inner = Box::into_raw(inner);
-
+    fn replace_inner(&self, current: *mut Inner<T>, inner: *mut Inner<T>) -> *mut Inner<T> {
         match self
             .inner
             .compare_exchange(current, inner, Ordering::AcqRel, Ordering::Acquire)
         {
             Ok(previous) => {
                 if !previous.is_null() {
-                    // Flipping every bit lets slots know to deallocate on the last dropped
-                    unsafe { &*previous }
-                        .occupancy
-                        .fetch_xor(u64::MAX, Ordering::Release);
+                    unsafe { Inner::retire(previous) }
                 }
 
                 inner
@@ -57,17 +74,80 @@ impl<T> Arena64<T> {
     }
 
     /// Inserts value into an unoccupied [`Slot`]
+    #[inline]
     pub fn insert(&self, value: T) -> Slot<T> {
+        self.insert_with_backoff(value, &mut NoBackoff)
+    }
+
+    /// Inserts value into an unoccupied [`Slot`], backing off after each lost
+    /// race to claim an index
+    pub fn insert_with_backoff(&self, value: T, backoff: &mut impl Backoff) -> Slot<T> {
         let mut inner = self.inner.load_consume();
 
         loop {
             if !inner.is_null() {
-                if let Some(slot) = unsafe { &*inner }.get_uninit_slot() {
+                if let Some(slot) = unsafe { &*inner }.get_uninit_slot_with_backoff(backoff) {
                     return slot.insert(value);
                 }
             }
 
-            inner = self.replace_inner(inner);
+            inner = self.replace_inner(inner, Inner::alloc());
+        }
+    }
+
+    /// Inserts value into an unoccupied [`Slot`], allocating a new slab as
+    /// needed but returning the value back should the allocator fail rather
+    /// than aborting
+    pub fn try_insert_growing(&self, value: T) -> Result<Slot<T>, (T, AllocError)> {
+        let mut inner = self.inner.load_consume();
+
+        loop {
+            if !inner.is_null() {
+                if let Some(slot) = unsafe { &*inner }.get_uninit_slot() {
+                    return Ok(slot.insert(value));
+                }
+            }
+
+            match Inner::try_alloc() {
+                Some(slab) => inner = self.replace_inner(inner, slab),
+                None => return Err((value, AllocError)),
+            }
+        }
+    }
+
+    /// Moves a boxed value into an unoccupied [`Slot`], deallocating the
+    /// [`Box`]
+    ///
+    /// The layouts differ, so this copies the value into the slab rather than
+    /// reusing the allocation
+    #[allow(clippy::boxed_local)]
+    pub fn insert_box(&self, value: Box<T>) -> Slot<T> {
+        self.insert(*value)
+    }
+
+    /// Consumes [`Arena64`], returning a raw pointer to its active slab, or
+    /// null if nothing was ever inserted
+    ///
+    /// # Safety
+    ///
+    /// For the slab to be retired this must be converted back into
+    /// [`Arena64`]
+    pub fn into_raw(self) -> *mut () {
+        let inner = self.inner.load_consume();
+        mem::forget(self);
+        inner.cast()
+    }
+
+    /// Reconstruct [`Arena64`] from a pointer to its active slab
+    ///
+    /// # Safety
+    ///
+    /// This pointer must have been created by [`Arena64::into_raw`] and
+    /// logically passes ownership; exactly one [`Arena64`] may own a given
+    /// slab at a time
+    pub unsafe fn from_raw(ptr: *mut ()) -> Self {
+        Arena64 {
+            inner: AtomicPtr::new(ptr.cast()),
         }
     }
 }
@@ -80,17 +160,136 @@ impl<T> Drop for Arena64<T> {
         let inner = self.inner.load_consume();
 
         if !inner.is_null() {
-            unsafe {
-                drop(Box::from_raw(inner));
-            }
+            // Slots from the active slab may still be alive, and so take over ownership
+            unsafe { Inner::retire(inner) }
         }
     }
 }
 
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// The default arena of each type used by the current thread
+    static ARENAS: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+/// Inserts into a lazily created [`Arena64`] of the current thread, one per
+/// type, for scratch allocations that don't warrant managing an arena
+///
+/// Each slot keeps its slab alive, so slots may outlive both the arena and
+/// the thread that created them, such as by being sent elsewhere. Once the
+/// thread exits its arena is retired, and each slab is deallocated as its
+/// last slot drops. Converting during thread-local destruction panics
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<T> From<T> for Slot<T>
+where
+    T: 'static,
+{
+    fn from(value: T) -> Self {
+        ARENAS.with_borrow_mut(|arenas| {
+            arenas
+                .entry(TypeId::of::<T>())
+                .or_insert_with(|| Box::new(Arena64::<T>::new()))
+                .downcast_ref::<Arena64<T>>()
+                .unwrap()
+                .insert(value)
+        })
+    }
+}
+
+impl<T> Slot<T> {
+    /// Attempts to convert the value with `f`, inserting the result into
+    /// `arena`, such as to narrow an enum to one of its variants
+    ///
+    /// The index held by this slot is only released on success. On failure
+    /// `f` returns the original value alongside the error, and this slot is
+    /// returned with the value written back
+    ///
+    /// # Aborts
+    ///
+    /// As with [`Slot::try_map_in_place`], if `f` panics the process aborts
+    pub fn try_cast<U, E>(
+        self,
+        arena: &Arena64<U>,
+        f: impl FnOnce(T) -> Result<U, (T, E)>,
+    ) -> Result<Slot<U>, (Slot<T>, E)> {
+        self.try_take_with(f).map(|value| arena.insert(value))
+    }
+}
+
+/// A concurrent arena storing metadata alongside each value within the same
+/// cell
+pub struct TaggedArena64<T, M> {
+    arena: Arena64<(T, M)>,
+}
+
+impl<T, M> Default for TaggedArena64<T, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, M> TaggedArena64<T, M> {
+    pub const fn new() -> Self {
+        TaggedArena64 {
+            arena: Arena64::new(),
+        }
+    }
+
+    /// Inserts value and metadata into an unoccupied [`TaggedSlot`]
+    pub fn insert(&self, value: T, meta: M) -> TaggedSlot<T, M> {
+        TaggedSlot {
+            slot: self.arena.insert((value, meta)),
+        }
+    }
+}
+
+/// Provides exclusive access over a value and its metadata until dropped
+pub struct TaggedSlot<T, M> {
+    slot: Slot<(T, M)>,
+}
+
+impl<T, M> TaggedSlot<T, M> {
+    pub fn value(&self) -> &T {
+        &self.slot.0
+    }
+
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.slot.0
+    }
+
+    pub fn meta(&self) -> &M {
+        &self.slot.1
+    }
+
+    pub fn meta_mut(&mut self) -> &mut M {
+        &mut self.slot.1
+    }
+
+    pub fn take(self) -> (T, M) {
+        self.slot.take()
+    }
+}
+
+impl<T, M> Debug for TaggedSlot<T, M>
+where
+    T: Debug,
+    M: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TaggedSlot")
+            .field("value", self.value())
+            .field("meta", self.meta())
+            .finish()
+    }
+}
+
 /// A bump allocator
 pub struct Bump64<T> {
     occupancy: u64,
     inner: *mut Inner<T>,
+    allocated: usize,
+    slabs: usize,
 }
 
 impl<T> Default for Bump64<T> {
@@ -104,6 +303,8 @@ impl<T> Bump64<T> {
         Bump64 {
             occupancy: 0,
             inner: ptr::null_mut(),
+            allocated: 0,
+            slabs: 0,
         }
     }
 
@@ -115,6 +316,7 @@ impl<T> Bump64<T> {
 
                 if least_significant_bit.ne(&0) {
                     self.occupancy |= least_significant_bit;
+                    self.allocated += 1;
 
                     let idx = least_significant_bit.trailing_zeros() as usize;
 
@@ -122,16 +324,117 @@ impl<T> Bump64<T> {
                         *(*self.inner).slots[idx].get() = MaybeUninit::new(value);
                     }
 
-                    return Slot {
-                        slab: self.inner,
-                        idx,
-                    };
+                    return unsafe { Slot::new(self.inner, idx) };
                 }
             }
 
-            self.inner = Box::into_raw(unsafe { Box::new_uninit().assume_init() });
+            self.inner = Inner::alloc();
             self.occupancy = 0;
+            self.slabs += 1;
+        }
+    }
+
+    /// Moves `count` values from `src` into the next slots, copying each run
+    /// of free indexes in bulk rather than moving values one by one
+    ///
+    /// # Safety
+    ///
+    /// `src` must point to `count` initialized values, which are logically
+    /// moved into the returned slots and must not be used or dropped
+    /// afterwards unless `T` is [`Copy`]. The source must not overlap any slab
+    pub unsafe fn alloc_copy_nonoverlapping(
+        &mut self,
+        src: *const T,
+        count: usize,
+    ) -> Vec<Slot<T>> {
+        let mut slots = Vec::with_capacity(count);
+
+        while slots.len().lt(&count) {
+            if self.inner.is_null() || self.occupancy.eq(&u64::MAX) {
+                self.inner = Inner::alloc();
+                self.occupancy = 0;
+                self.slabs += 1;
+            }
+
+            // Run of free indexes starting from the lowest
+            let start = self.occupancy.trailing_ones() as usize;
+            let run = (!self.occupancy >> start).trailing_ones() as usize;
+            let len = run.min(count - slots.len());
+
+            ptr::copy_nonoverlapping(
+                src.add(slots.len()),
+                (*self.inner).slots[start].get().cast::<T>(),
+                len,
+            );
+
+            self.occupancy |= (u64::MAX >> (64 - len)) << start;
+            self.allocated += len;
+
+            let inner = self.inner;
+            slots.extend((start..start + len).map(|idx| Slot::new(inner, idx)));
         }
+
+        slots
+    }
+
+    /// Copies every value of `values` into the next slots in bulk
+    pub fn alloc_from_slice(&mut self, values: &[T]) -> Vec<Slot<T>>
+    where
+        T: Copy,
+    {
+        unsafe { self.alloc_copy_nonoverlapping(values.as_ptr(), values.len()) }
+    }
+
+    /// The total number of values ever allocated, including those whose
+    /// indexes were since reclaimed by [`Bump64::scope`]
+    pub fn allocated_count(&self) -> usize {
+        self.allocated
+    }
+
+    /// The number of slabs ever created
+    pub fn slab_count(&self) -> usize {
+        self.slabs
+    }
+
+    /// Runs `f` and afterwards reclaims the indexes it allocated from the
+    /// active slab so that they can be reused, avoiding a new slab per
+    /// iteration of scratch allocations
+    ///
+    /// Every [`Slot`] allocated within `f` is expected to have dropped by the
+    /// time it returns. Slots escaping the scope are a logic error caught by a
+    /// debug assertion; otherwise their indexes remain allocated. Should `f`
+    /// fill the active slab, it is left to its slots to deallocate and the
+    /// next insertion starts a new slab
+    pub fn scope<R>(&mut self, f: impl FnOnce(&mut Bump64<T>) -> R) -> R {
+        let inner = self.inner;
+        let occupancy = self.occupancy;
+
+        let result = f(self);
+
+        // A full slab is owned by its slots, the last of which deallocates it, so it
+        // may already be gone
+        if self.occupancy.eq(&u64::MAX) {
+            self.inner = ptr::null_mut();
+            self.occupancy = 0;
+        } else if !self.inner.is_null() {
+            let allocated = if self.inner.eq(&inner) {
+                self.occupancy & !occupancy
+            } else {
+                self.occupancy
+            };
+
+            // Dropped slots have their bits set because bits weren't set when occupying
+            let released = unsafe { &*self.inner }
+                .occupancy
+                .fetch_and(!allocated, Ordering::AcqRel)
+                & allocated;
+
+            self.occupancy &= !released;
+
+            debug_assert_eq!(released, allocated, "Slot escaped Bump64::scope");
+        }
+
+        result
     }
 }
 
@@ -153,7 +456,7 @@ impl<T> Drop for Bump64<T> {
             // If every bit has already been set, then every [`Slot`] has dropped
             if released.eq(&self.occupancy) {
                 unsafe {
-                    drop(Box::from_raw(self.inner));
+                    Inner::dealloc(self.inner);
                 }
             }
         }
@@ -162,9 +465,9 @@ impl<T> Drop for Bump64<T> {
 
 #[cfg(test)]
 mod tests {
-    use alloc::vec::Vec;
+    use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
 
-    use crate::arena::{Arena64, Bump64, Slot};
+    use crate::arena::{Arena64, Bump64, Slot, TaggedArena64, TaggedSlot};
 
     #[test]
     fn arena64_capacity_grows() {
@@ -175,6 +478,85 @@ mod tests {
         assert_eq!(slots, (0..4096).collect::<Vec<u32>>())
     }
 
+    #[test]
+    fn arena64_drops_before_active_slots() {
+        let counter = Arc::new(());
+        let arena = Arena64::new();
+
+        let slots: Vec<Slot<Arc<()>>> = (0..3).map(|_| arena.insert(counter.clone())).collect();
+
+        drop(arena);
+        assert_eq!(Arc::strong_count(&counter), 4);
+
+        drop(slots);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn arena64_round_trips_through_raw() {
+        let arena = Arena64::new();
+        let first = arena.insert(0);
+
+        let ptr = arena.into_raw();
+        let arena = unsafe { Arena64::from_raw(ptr) };
+
+        let second = arena.insert(1);
+        assert_eq!(second.index(), first.index() + 1);
+
+        let empty = Arena64::<usize>::new().into_raw();
+        assert!(empty.is_null());
+        drop(unsafe { Arena64::<usize>::from_raw(empty) });
+    }
+
+    #[test]
+    fn narrows_slots_between_arenas() {
+        let wide = Arena64::<i64>::new();
+        let narrow = Arena64::<u8>::new();
+
+        let narrowing = |value: i64| u8::try_from(value).map_err(|err| (value, err));
+
+        let slot = wide.insert(42).try_cast(&narrow, narrowing).unwrap();
+        assert_eq!(slot, 42);
+
+        let negative = wide.insert(-1);
+        let idx = negative.index();
+
+        let (negative, _) = negative.try_cast(&narrow, narrowing).unwrap_err();
+        assert_eq!(negative, -1);
+        assert_eq!(negative.index(), idx);
+        assert_eq!(narrow.insert(0).index(), 1);
+    }
+
+    #[test]
+    fn tagged_arena64_stores_meta_with_value() {
+        let arena = TaggedArena64::new();
+
+        let mut slots: Vec<TaggedSlot<u32, u8>> =
+            (0..128).map(|i| arena.insert(i, (i % 4) as u8)).collect();
+
+        for slot in slots.iter_mut() {
+            *slot.meta_mut() += 1;
+            *slot.value_mut() *= 2;
+        }
+
+        for (i, slot) in slots.into_iter().enumerate() {
+            assert_eq!(*slot.value(), i as u32 * 2);
+            assert_eq!(*slot.meta(), (i % 4) as u8 + 1);
+            assert_eq!(slot.take(), (i as u32 * 2, (i % 4) as u8 + 1));
+        }
+    }
+
+    #[test]
+    fn arena64_converts_into_and_from_box() {
+        let arena = Arena64::new();
+
+        let slot = arena.insert_box(Box::new(String::from("boxed")));
+        assert_eq!(slot, String::from("boxed"));
+
+        let value: Box<String> = slot.into_box();
+        assert_eq!(*value, "boxed");
+    }
+
     #[test]
     fn bump64_capacity_grows() {
         let mut arena = Bump64::new();
@@ -183,4 +565,134 @@ mod tests {
 
         assert_eq!(slots, (0..4096).collect::<Vec<u32>>())
     }
+
+    #[test]
+    fn bump64_copies_slices_in_bulk() {
+        let mut arena = Bump64::new();
+        let values: Vec<u32> = (0..200).collect();
+
+        let first = arena.insert(u32::MAX);
+        let slots = arena.alloc_from_slice(&values);
+
+        assert_eq!(slots, values);
+        assert!(slots[..63].iter().all(|slot| slot.slab().eq(&first.slab())));
+        assert!(slots[63..].iter().all(|slot| slot.slab().ne(&first.slab())));
+
+        let remaining = arena.alloc_from_slice(&[]);
+        assert!(remaining.is_empty());
+    }
+
+    #[test]
+    fn bump64_counts_allocations_and_slabs() {
+        let mut arena = Bump64::new();
+        assert_eq!((arena.allocated_count(), arena.slab_count()), (0, 0));
+
+        let slots: Vec<Slot<usize>> = (0..100).map(|i| arena.insert(i)).collect();
+        assert_eq!((arena.allocated_count(), arena.slab_count()), (100, 2));
+
+        arena.scope(|arena| {
+            arena.alloc_from_slice(&[0; 20]);
+        });
+        assert_eq!((arena.allocated_count(), arena.slab_count()), (120, 2));
+
+        drop(slots);
+    }
+
+    #[test]
+    fn bump64_scope_reuses_slab() {
+        let mut arena = Bump64::new();
+
+        let outer = arena.insert(0);
+        let inner = arena.inner;
+
+        for _ in 0..8 {
+            let sum = arena.scope(|arena| {
+                let slots: Vec<Slot<u32>> = (1..63).map(|i| arena.insert(i)).collect();
+
+                slots.iter().map(|slot| **slot).sum::<u32>()
+            });
+
+            assert_eq!(sum, 1953);
+            assert_eq!(arena.inner, inner);
+            assert_eq!(arena.occupancy, 1);
+        }
+
+        assert_eq!(outer, 0);
+    }
+
+    #[test]
+    fn bump64_scope_filling_slab() {
+        let mut arena = Bump64::new();
+
+        for _ in 0..2 {
+            arena.scope(|arena| {
+                let slots: Vec<Slot<usize>> = (0..64).map(|i| arena.insert(i)).collect();
+                drop(slots);
+            });
+
+            assert!(arena.inner.is_null());
+        }
+
+        assert_eq!(*arena.insert(7), 7);
+        assert_eq!(arena.slab_count(), 3);
+    }
+
+    #[test]
+    fn bump64_scope_rolling_over() {
+        let mut arena = Bump64::new();
+
+        arena.scope(|arena| {
+            let slots: Vec<Slot<usize>> = (0..65).map(|i| arena.insert(i)).collect();
+            drop(slots);
+        });
+
+        assert!(!arena.inner.is_null());
+        assert_eq!(arena.occupancy, 0);
+
+        arena.scope(|arena| {
+            let slots: Vec<Slot<usize>> = (0..128).map(|i| arena.insert(i)).collect();
+            drop(slots);
+        });
+
+        assert!(arena.inner.is_null());
+        assert_eq!(*arena.insert(7), 7);
+        assert_eq!(arena.slab_count(), 4);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Slot escaped Bump64::scope")]
+    fn bump64_scope_detects_escaped_slots() {
+        let mut arena = Bump64::new();
+
+        let _escaped = arena.scope(|arena| arena.insert(0));
+    }
+
+    #[test]
+    fn try_insert_growing_grows() {
+        let arena = Arena64::new();
+
+        let slots: Vec<Slot<u32>> = (0..256)
+            .map(|i| arena.try_insert_growing(i).ok().unwrap())
+            .collect();
+
+        assert_eq!(slots, (0..256).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn converts_into_thread_local_arena() {
+        let first: Slot<u32> = 1.into();
+        let second: Slot<u32> = Slot::from(2);
+        let text: Slot<String> = String::from("three").into();
+
+        assert_eq!((*first, *second, text.as_str()), (1, 2, "three"));
+        assert_eq!(first.slab_addr(), second.slab_addr());
+
+        let sent: Vec<Slot<u32>> = std::thread::spawn(|| (0..100).map(Slot::from).collect())
+            .join()
+            .unwrap();
+
+        assert_eq!(sent, (0..100).collect::<Vec<u32>>());
+    }
 }
@@ -0,0 +1,52 @@
+use core::hint::spin_loop;
+
+/// Strategy for backing off after losing the race to claim an index, reducing
+/// cache-line contention on the occupancy word
+pub trait Backoff {
+    /// Called after each lost race prior to retrying
+    fn backoff(&mut self);
+}
+
+/// Retries immediately
+pub(crate) struct NoBackoff;
+
+impl Backoff for NoBackoff {
+    #[inline(always)]
+    fn backoff(&mut self) {}
+}
+
+/// Exponential backoff using [`spin_loop`] hints
+#[derive(Debug, Default)]
+pub struct SpinBackoff {
+    step: u32,
+}
+
+impl SpinBackoff {
+    const SPIN_LIMIT: u32 = 6;
+
+    pub const fn new() -> Self {
+        SpinBackoff { step: 0 }
+    }
+}
+
+impl Backoff for SpinBackoff {
+    #[inline]
+    fn backoff(&mut self) {
+        for _ in 0..1 << self.step {
+            spin_loop();
+        }
+
+        if self.step < Self::SPIN_LIMIT {
+            self.step += 1;
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "extern_crate_alloc")))]
+#[cfg(feature = "extern_crate_alloc")]
+impl Backoff for crossbeam_utils::Backoff {
+    #[inline]
+    fn backoff(&mut self) {
+        self.spin();
+    }
+}
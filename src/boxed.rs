@@ -1,47 +1,282 @@
-use alloc::boxed::Box;
+use alloc::{alloc::handle_alloc_error, boxed::Box, string::String, vec::Vec};
+#[cfg(feature = "shared")]
+use core::sync::atomic::{fence, AtomicU32, AtomicUsize};
 use core::{
-    cell::UnsafeCell,
-    fmt::Debug,
+    alloc::Layout,
+    borrow::Borrow,
+    cell::{Cell, UnsafeCell},
+    cmp,
+    error::Error,
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    iter,
     mem::{self, forget, ManuallyDrop, MaybeUninit},
-    ops::{Deref, DerefMut},
-    ptr::addr_of,
+    ops::{Deref, DerefMut, Index, IndexMut},
+    pin::Pin,
+    ptr::{addr_of, addr_of_mut, NonNull},
+    slice,
     sync::atomic::{AtomicU64, Ordering},
 };
 
-use crate::{IDX, IDX_MASK};
+use crate::{
+    backoff::{Backoff, NoBackoff},
+    free_indices,
+    heapless::Fixed64,
+    IDX, IDX_MASK,
+};
 
-#[repr(align(64))]
+/// `repr(C)` fixes the field order so that the offset of `slots` depends only
+/// on the alignment of `T`, which [`Slot::cast`] relies upon
+#[repr(C, align(64))]
 pub(crate) struct Inner<T> {
     pub(crate) occupancy: AtomicU64,
+    /// Indexes converted by [`Slot::into_raw_checked`] and not yet
+    /// reconstructed
+    pub(crate) raw: AtomicU64,
+    /// Reservations converted by [`UninitSlot::into_raw`] and not yet
+    /// reconstructed, kept apart from `raw` so that neither can claim the other
+    #[cfg(debug_assertions)]
+    pub(crate) reserved: AtomicU64,
+    /// Weak handles pinning this allocation, plus one held collectively by
+    /// the slots
+    #[cfg(feature = "shared")]
+    pub(crate) weak: AtomicUsize,
+    /// Per slot generation and [`SharedSlot`](crate::shared::SharedSlot)
+    /// reference count
+    #[cfg(feature = "shared")]
+    pub(crate) state: [AtomicU32; 64],
+    #[cfg(feature = "notify")]
+    pub(crate) hooks: OccupancyHooks,
     pub(crate) slots: [UnsafeCell<MaybeUninit<T>>; 64],
 }
 
 impl<T> Inner<T> {
+    /// Allocate on the heap with every slot unoccupied
+    pub(crate) fn alloc() -> *mut Inner<T> {
+        Inner::try_alloc().unwrap_or_else(|| handle_alloc_error(Layout::new::<Inner<T>>()))
+    }
+
+    /// Allocate on the heap with every slot unoccupied, or return [`None`]
+    /// should the allocator fail
+    pub(crate) fn try_alloc() -> Option<*mut Inner<T>> {
+        // Allocated with the layout of Inner so that it can be freed as a Box
+        let inner = unsafe { alloc::alloc::alloc(Layout::new::<Inner<T>>()) }.cast::<Inner<T>>();
+
+        if inner.is_null() {
+            return None;
+        }
+
+        unsafe {
+            addr_of_mut!((*inner).occupancy).write(AtomicU64::new(0));
+            addr_of_mut!((*inner).raw).write(AtomicU64::new(0));
+            #[cfg(debug_assertions)]
+            addr_of_mut!((*inner).reserved).write(AtomicU64::new(0));
+            #[cfg(feature = "shared")]
+            addr_of_mut!((*inner).weak).write(AtomicUsize::new(1));
+            #[cfg(feature = "shared")]
+            addr_of_mut!((*inner).state).write([const { AtomicU32::new(0) }; 64]);
+            #[cfg(feature = "notify")]
+            addr_of_mut!((*inner).hooks).write(OccupancyHooks::default());
+        }
+
+        Some(inner)
+    }
+
+    /// Release ownership of an index
+    ///
+    /// # Safety
+    ///
+    /// The index must be owned by the caller and the cell must not be accessed
+    /// afterwards as the slab may have been deallocated
+    pub(crate) unsafe fn release(slab: *const Inner<T>, idx: usize) {
+        // Invalidate weak handles to the value held at this index
+        #[cfg(feature = "shared")]
+        (*slab).state[idx].fetch_add(crate::shared::GENERATION, Ordering::Relaxed);
+
+        // Read beforehand as the slab may be deallocated once this index is released
+        #[cfg(feature = "notify")]
+        let hooks = (*slab).hooks;
+
+        // Release publishes this owner's use of the cell to whoever claims the index
+        // next, and acquire lets the last slot of a retired slab observe every other
+        // release before deallocating
+        let occupancy = (*slab).occupancy.fetch_xor(1 << idx, Ordering::AcqRel);
+
+        // If this was the last slot after Boxed64 was previously dropped, then the
+        // underlying heap allocation needs to be dropped
+        if occupancy.eq(&!(1 << idx)) {
+            Inner::dealloc(slab);
+        }
+
+        // Only possible while not retired, as retiring clears the bits of occupied
+        // indexes
+        #[cfg(feature = "notify")]
+        if occupancy.eq(&(1 << idx)) {
+            if let Some(on_became_empty) = hooks.on_became_empty {
+                on_became_empty();
+            }
+        }
+    }
+
+    /// Release ownership of every index in `mask` with a single atomic
+    /// operation
+    ///
+    /// # Safety
+    ///
+    /// As with [`Inner::release`], for every index in `mask`
+    pub(crate) unsafe fn release_many(slab: *const Inner<T>, mask: u64) {
+        #[cfg(feature = "shared")]
+        for idx in free_indices(!mask) {
+            (*slab).state[idx].fetch_add(crate::shared::GENERATION, Ordering::Relaxed);
+        }
+
+        #[cfg(feature = "notify")]
+        let hooks = (*slab).hooks;
+
+        let occupancy = (*slab).occupancy.fetch_xor(mask, Ordering::AcqRel);
+
+        if occupancy.eq(&!mask) {
+            Inner::dealloc(slab);
+        }
+
+        #[cfg(feature = "notify")]
+        if occupancy.eq(&mask) {
+            if let Some(on_became_empty) = hooks.on_became_empty {
+                on_became_empty();
+            }
+        }
+    }
+
+    /// Hands ownership of the slab over to its outstanding slots, deallocating
+    /// immediately if there are none
+    ///
+    /// # Safety
+    ///
+    /// The slab must not be accessed by the caller afterwards, and must not
+    /// already have been retired
+    pub(crate) unsafe fn retire(slab: *const Inner<T>) {
+        // Flipping every bit lets slots know to deallocate on the last dropped
+        let occupancy = (*slab).occupancy.fetch_xor(u64::MAX, Ordering::AcqRel);
+
+        if occupancy.eq(&0) {
+            Inner::dealloc(slab);
+        }
+    }
+
+    /// Deallocate once no slots remain, deferring to the last weak handle if
+    /// any are outstanding
+    ///
+    /// # Safety
+    ///
+    /// Every index must have been released and the slab retired
+    pub(crate) unsafe fn dealloc(slab: *const Inner<T>) {
+        #[cfg(feature = "shared")]
+        {
+            if (*slab).weak.fetch_sub(1, Ordering::Release).ne(&1) {
+                return;
+            }
+
+            fence(Ordering::Acquire);
+        }
+
+        drop(Box::from_raw(slab as *mut Inner<T>));
+    }
+
+    /// Pointer to the cell at `idx`, derived without creating intermediate
+    /// references
+    ///
+    /// # Safety
+    ///
+    /// The slab must be live and `idx` less than 64
+    #[inline]
+    pub(crate) unsafe fn cell(slab: *const Inner<T>, idx: usize) -> NonNull<T> {
+        NonNull::new_unchecked(addr_of!((*slab).slots).cast::<T>().add(idx).cast_mut())
+    }
+
+    /// Recovers the slab from a pointer to the cell at `idx`, the inverse of
+    /// [`Inner::cell`]
+    #[inline]
+    pub(crate) fn slab_of(cell: NonNull<T>, idx: usize) -> *const Inner<T> {
+        cell.as_ptr()
+            .wrapping_byte_sub(mem::offset_of!(Inner<T>, slots) + idx * mem::size_of::<T>())
+            .cast()
+    }
+
+    /// The number of unoccupied indexes
+    #[inline(always)]
+    pub(crate) fn available_count(&self) -> u32 {
+        self.occupancy.load(Ordering::Acquire).count_zeros()
+    }
+
+    /// The number of occupied indexes
+    #[inline(always)]
+    pub(crate) fn occupied_count(&self) -> u32 {
+        self.occupancy.load(Ordering::Acquire).count_ones()
+    }
+
+    /// Whether every index is occupied
+    #[inline(always)]
+    pub(crate) fn is_full(&self) -> bool {
+        self.occupied_count().eq(&64)
+    }
+
+    /// Whether no index is occupied
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.occupied_count().eq(&0)
+    }
+
     /// Get an unoccupied [`UninitSlot`] if available
+    #[inline]
     pub(crate) fn get_uninit_slot(&self) -> Option<UninitSlot<T>> {
-        let mut occupancy = self.occupancy.load(Ordering::Acquire);
+        self.get_uninit_slot_with_backoff(&mut NoBackoff)
+    }
+
+    /// Get an unoccupied [`UninitSlot`] if available, backing off after each
+    /// lost race to claim an index
+    pub(crate) fn get_uninit_slot_with_backoff(
+        &self,
+        backoff: &mut impl Backoff,
+    ) -> Option<UninitSlot<T>> {
+        // Only a hint for which index to claim. Ownership is established by the
+        // fetch_or below, whose acquire synchronizes with the release of the
+        // index by its previous owner, so no ordering is needed here; unlike
+        // consume, relaxed also avoids any barrier on weakly ordered targets
+        let mut occupancy = self.occupancy.load(Ordering::Relaxed);
 
         let idx = loop {
             // Isolate lowest clear bit. See https://docs.rs/bitintr/latest/bitintr/trait.Blcic.html
+            // Adding one carries through the trailing set bits into the lowest
+            // clear bit, which is the only bit set in both. The addition must
+            // wrap rather than overflow: when full, u64::MAX wraps to 0, leaving
+            // no bit to claim
             let least_significant_bit = !occupancy & (occupancy.wrapping_add(1));
 
             if least_significant_bit.ne(&0) {
+                // Acquire synchronizes with the release of this index by its previous
+                // owner before the cell is reused
                 occupancy = self
                     .occupancy
                     .fetch_or(least_significant_bit, Ordering::AcqRel);
 
                 if (occupancy & least_significant_bit).eq(&0) {
+                    #[cfg(feature = "notify")]
+                    if (occupancy | least_significant_bit).eq(&u64::MAX) {
+                        if let Some(on_became_full) = self.hooks.on_became_full {
+                            on_became_full();
+                        }
+                    }
+
                     break least_significant_bit.trailing_zeros();
                 }
+
+                backoff.backoff();
             } else {
                 return None;
             }
         };
 
-        Some(UninitSlot {
-            slab: addr_of!(*self),
-            idx: idx as usize,
-        })
+        Some(unsafe { UninitSlot::new(addr_of!(*self), idx as usize) })
     }
 }
 
@@ -61,8 +296,21 @@ impl<T> Default for Boxed64<T> {
 impl<T> Boxed64<T> {
     /// Create with a fixed capacity of 64
     pub fn new() -> Self {
-        let inner: Box<Inner<T>> = unsafe { Box::new_uninit().assume_init() };
-        let inner = Box::into_raw(inner);
+        Boxed64 {
+            inner: Inner::alloc(),
+        }
+    }
+
+    /// Create with a fixed capacity of 64, invoking `hooks` as the slab
+    /// becomes full or empty
+    #[cfg(feature = "notify")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+    pub fn with_hooks(hooks: OccupancyHooks) -> Self {
+        let inner = Inner::alloc();
+
+        unsafe {
+            (*inner).hooks = hooks;
+        }
 
         Boxed64 { inner }
     }
@@ -72,9 +320,167 @@ impl<T> Boxed64<T> {
     }
 
     /// Get an unoccupied [`UninitSlot`] if available
+    ///
+    /// The lowest unoccupied index is claimed, so slots are handed out in
+    /// index order while none are released. Returns [`None`] without blocking
+    /// when all 64 indexes are occupied
     pub fn get_uninit_slot(&self) -> Option<UninitSlot<T>> {
         self.inner().get_uninit_slot()
     }
+
+    /// Iterate over the indexes unoccupied at the time of calling. Other
+    /// threads may concurrently claim or release indexes, so this is only a
+    /// snapshot
+    pub fn free_indices(&self) -> impl Iterator<Item = usize> {
+        free_indices(self.inner().occupancy.load(Ordering::Acquire))
+    }
+
+    /// The number of unoccupied indexes at the time of calling
+    pub fn available_count(&self) -> u32 {
+        self.inner().available_count()
+    }
+
+    /// The number of occupied indexes at the time of calling
+    pub fn occupied_count(&self) -> u32 {
+        self.inner().occupied_count()
+    }
+
+    /// Whether every index was occupied at the time of calling
+    pub fn is_full(&self) -> bool {
+        self.inner().is_full()
+    }
+
+    /// Whether no index was occupied at the time of calling
+    pub fn is_empty(&self) -> bool {
+        self.inner().is_empty()
+    }
+
+    /// Views every cell as a contiguous array when all 64 indexes are
+    /// occupied, or returns [`None`] otherwise
+    ///
+    /// # Safety
+    ///
+    /// Every index must be owned by an initialized [`Slot`] rather than an
+    /// [`UninitSlot`], and no [`Slot`] may mutate or take its value for the
+    /// lifetime of the returned reference
+    pub unsafe fn as_slice(&self) -> Option<&[T; 64]> {
+        if self.inner().is_full() {
+            Some(&*(self.inner().slots.as_ptr() as *const [T; 64]))
+        } else {
+            None
+        }
+    }
+
+    /// Returns a reference to the value at `idx` without checking occupancy
+    ///
+    /// # Safety
+    ///
+    /// `idx` must be less than 64 and owned by an initialized [`Slot`], which
+    /// must neither mutate nor take its value for the lifetime of the
+    /// returned reference
+    pub unsafe fn get_unchecked(&self, idx: usize) -> &T {
+        (*self.inner().slots.get_unchecked(idx).get()).assume_init_ref()
+    }
+
+    /// Returns a mutable reference to the value at `idx` without checking
+    /// occupancy
+    ///
+    /// # Safety
+    ///
+    /// `idx` must be less than 64 and owned by an initialized [`Slot`], which
+    /// must not access its value for the lifetime of the returned reference.
+    /// Taking `&mut self` only rules out overlapping calls through
+    /// [`Boxed64`], as slots are accessed independently of it
+    pub unsafe fn get_unchecked_mut(&mut self, idx: usize) -> &mut T {
+        (*self.inner().slots.get_unchecked(idx).get()).assume_init_mut()
+    }
+
+    /// Get an unoccupied [`UninitSlot`] if available, backing off after each
+    /// lost race to claim an index
+    pub fn get_uninit_slot_with_backoff(
+        &self,
+        backoff: &mut impl Backoff,
+    ) -> Option<UninitSlot<T>> {
+        self.inner().get_uninit_slot_with_backoff(backoff)
+    }
+
+    /// The occupancy bitmask, where each set bit is a claimed index
+    #[cfg(any(test, feature = "test-util"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+    pub fn occupancy_for_test(&self) -> u64 {
+        self.inner().occupancy.load(Ordering::Acquire)
+    }
+
+    /// Overwrites the occupancy bitmask to set up an exact state, such as to
+    /// exercise claiming around particular indexes
+    ///
+    /// Indexes set here without a [`Slot`] are never released, so the
+    /// allocation leaks once [`Boxed64`] drops unless they're cleared again
+    ///
+    /// # Safety
+    ///
+    /// The bits of indexes held by outstanding slots must remain set
+    #[cfg(any(test, feature = "test-util"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+    pub unsafe fn set_occupancy_for_test(&self, bits: u64) {
+        self.inner().occupancy.store(bits, Ordering::Release);
+    }
+
+    /// Defers releasing indexes of slots managed by the returned guard until
+    /// it drops, so that tearing down many slots costs a single atomic
+    /// operation rather than one per slot
+    pub fn drain_guard(&self) -> SlabDrainGuard<'_, T> {
+        SlabDrainGuard {
+            slab: self,
+            released: Cell::new(0),
+        }
+    }
+
+    /// Inserts values into free indexes, pushing each [`Slot`] into `slots`
+    /// until either `values` is exhausted or [`Boxed64`] is full. Returns the
+    /// number of values inserted
+    ///
+    /// An index is claimed before each value is taken, so values that didn't
+    /// fit remain in the iterator when passed by reference, such as with
+    /// [`Iterator::by_ref`]
+    pub fn extend(
+        &self,
+        slots: &mut impl Extend<Slot<T>>,
+        values: impl IntoIterator<Item = T>,
+    ) -> usize {
+        let mut values = values.into_iter();
+        let mut count = 0;
+
+        slots.extend(iter::from_fn(|| {
+            let uninit = self.get_uninit_slot()?;
+            let value = values.next()?;
+
+            count += 1;
+
+            Some(uninit.insert(value))
+        }));
+
+        count
+    }
+
+    /// Inserts every value or none of them, returning the values back should
+    /// fewer than `N` indexes be free. Arrays longer than 64 fail to compile
+    pub fn try_extend_exact<const N: usize>(&self, values: [T; N]) -> Result<[Slot<T>; N], [T; N]> {
+        const { assert!(N <= 64, "Boxed64 has a capacity of 64") };
+
+        let uninit: Vec<UninitSlot<T>> = iter::repeat_with(|| self.get_uninit_slot())
+            .take(N)
+            .map_while(|uninit| uninit)
+            .collect();
+
+        if uninit.len().lt(&N) {
+            return Err(values);
+        }
+
+        let mut uninit = uninit.into_iter();
+
+        Ok(values.map(|value| uninit.next().unwrap().insert(value)))
+    }
 }
 
 unsafe impl<T> Send for Boxed64<T> where T: Send {}
@@ -82,266 +488,2807 @@ unsafe impl<T> Sync for Boxed64<T> where T: Sync {}
 
 impl<T> Drop for Boxed64<T> {
     fn drop(&mut self) {
-        // Flipping every bit lets slots know to deallocate on the last dropped
-        let occupancy = self.inner().occupancy.fetch_xor(u64::MAX, Ordering::AcqRel);
+        unsafe { Inner::retire(self.inner) }
+    }
+}
 
-        if occupancy.eq(&0) {
-            unsafe {
-                drop(Box::from_raw(self.inner));
-            }
+/// A heap-allocated [`Fixed64`] that strictly owns its storage, issuing
+/// slots that borrow it
+///
+/// Unlike [`Boxed64`], slots can't outlive the slab, so there are no
+/// detachable handles; in exchange ownership is simpler, and dropping a slot
+/// never needs to check whether it was the last
+///
+/// ```compile_fail
+/// use arena64::boxed::BoxedOwned64;
+///
+/// let slab = BoxedOwned64::new();
+/// let slot = slab.get_uninit_slot().unwrap().insert(1);
+///
+/// drop(slab);
+/// drop(slot);
+/// ```
+pub struct BoxedOwned64<T> {
+    inner: Box<Fixed64<T>>,
+}
+
+impl<T> Default for BoxedOwned64<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> BoxedOwned64<T> {
+    /// Create with a fixed capacity of 64
+    pub fn new() -> Self {
+        BoxedOwned64 {
+            inner: Fixed64::new_boxed(),
         }
     }
 }
 
+impl<T> Deref for BoxedOwned64<T> {
+    type Target = Fixed64<T>;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+/// Callbacks invoked as a [`Boxed64`] transitions between being full or empty,
+/// registered with [`Boxed64::with_hooks`]
+///
+/// Hooks run on whichever thread claimed the last free index or released the
+/// last occupied one, and are plain `fn` pointers so they cannot capture
+/// state. Transitions are not reported once [`Boxed64`] has dropped
+#[cfg(feature = "notify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "notify")))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OccupancyHooks {
+    /// Invoked when claiming the last free index
+    pub on_became_full: Option<fn()>,
+    /// Invoked when releasing the last occupied index
+    pub on_became_empty: Option<fn()>,
+}
+
 /// Provides exclusive access over an unitialized index of [`Boxed64`] until
 /// dropped
+///
+/// [`UninitSlot`] is [`Send`] whenever `T` is, so an index can be reserved on
+/// one thread, initialized on another, and the resulting [`Slot`] used and
+/// dropped on yet another. The slab stays allocated until the last of these
+/// handles drops, even if [`Boxed64`] has been dropped in the meantime
 pub struct UninitSlot<T> {
-    slab: *const Inner<T>,
+    cell: NonNull<T>,
     idx: usize,
 }
 
 impl<T> UninitSlot<T> {
-    fn inner(&self) -> &Inner<T> {
-        unsafe { &*self.slab }
+    /// # Safety
+    ///
+    /// The index must be claimed and is released once dropped
+    pub(crate) unsafe fn new(slab: *const Inner<T>, idx: usize) -> Self {
+        UninitSlot {
+            cell: Inner::cell(slab, idx),
+            idx,
+        }
     }
 
-    /// Initialize slot with value
-    pub fn insert(self, value: T) -> Slot<T> {
-        unsafe {
-            *self.inner().slots[self.idx].get() = MaybeUninit::new(value);
-        }
+    pub(crate) fn slab(&self) -> *const Inner<T> {
+        Inner::slab_of(self.cell, self.idx)
+    }
 
-        unsafe { mem::transmute(self) }
+    /// The index of this slot within [`Boxed64`]
+    pub fn index(&self) -> usize {
+        self.idx
     }
-}
 
-unsafe impl<T> Send for UninitSlot<T> where T: Send {}
-unsafe impl<T> Sync for UninitSlot<T> where T: Sync {}
+    /// The address of the underlying [`Boxed64`] allocation, which together
+    /// with [`UninitSlot::index`] identifies the cell before it's initialized
+    ///
+    /// ```
+    /// use arena64::boxed::{Boxed64, Slot};
+    ///
+    /// struct Descriptor {
+    ///     tag: usize,
+    ///     len: usize,
+    /// }
+    ///
+    /// let slab = Boxed64::<[u8; 64]>::new();
+    /// let uninit = slab.get_uninit_slot().unwrap();
+    ///
+    /// // Handed back on completion to find the buffer
+    /// let descriptor = Descriptor {
+    ///     tag: uninit.slab_addr() | uninit.index(),
+    ///     len: 64,
+    /// };
+    ///
+    /// let raw = uninit.insert([0; 64]).into_raw();
+    /// assert_eq!(raw.addr(), descriptor.tag);
+    ///
+    /// let slot = unsafe { Slot::<[u8; 64]>::from_raw(raw) };
+    /// assert_eq!(slot.len(), descriptor.len);
+    /// ```
+    pub fn slab_addr(&self) -> usize {
+        self.slab().addr()
+    }
 
-impl<T> Drop for UninitSlot<T> {
-    fn drop(&mut self) {
-        let occupancy = self
-            .inner()
-            .occupancy
-            .fetch_xor(1 << self.idx, Ordering::AcqRel);
+    /// Returns a raw pointer to the uninitialized cell, derived directly from
+    /// the [`UnsafeCell`] without creating intermediate references
+    pub fn as_ptr(&self) -> *const T {
+        self.cell.as_ptr()
+    }
 
-        // If this was the last slot after Boxed64 was previously dropped, then the
-        // underlying heap allocation needs to be dropped
-        if occupancy.eq(&!(1 << self.idx)) {
-            unsafe {
-                drop(Box::from_raw(self.slab as *mut Inner<T>));
-            }
-        }
+    /// Returns a raw mutable pointer to the uninitialized cell, derived
+    /// directly from the [`UnsafeCell`] without creating intermediate
+    /// references
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.cell.as_ptr()
     }
-}
 
-/// Provides exclusive access over an index of [`Boxed64`] until dropped
-pub struct Slot<T> {
-    pub(crate) slab: *const Inner<T>,
-    pub(crate) idx: usize,
-}
+    /// Initialize slot with value
+    pub fn insert(self, value: T) -> Slot<T> {
+        self.insert_with(|| value)
+    }
 
-impl<T> Slot<T> {
-    fn inner(&self) -> &Inner<T> {
-        unsafe { &*self.slab }
+    /// Holds the reservation until `fut` resolves, then initializes slot with
+    /// its output. Dropping the returned future before then, such as on
+    /// cancellation, releases the index
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub async fn insert_future(self, fut: impl core::future::Future<Output = T>) -> Slot<T> {
+        let value = fut.await;
+        self.insert(value)
     }
 
-    pub fn take(self) -> T {
-        let value = unsafe {
-            mem::replace(
-                &mut *self.inner().slots[self.idx].get(),
-                MaybeUninit::uninit(),
-            )
-            .assume_init()
-        };
+    /// Initialize slot with value, pinning it immediately so that a `!Unpin`
+    /// value is never reachable through a movable [`Slot`]
+    pub fn insert_pinned(self, value: T) -> Pin<Slot<T>> {
+        self.insert(value).into_pin()
+    }
 
-        let occupancy = self
-            .inner()
-            .occupancy
-            .fetch_xor(1 << self.idx, Ordering::AcqRel);
+    /// Initialize slot with the value returned by `f`, which only runs once
+    /// the index is reserved. Should `f` panic, the index is released
+    pub fn insert_with(self, f: impl FnOnce() -> T) -> Slot<T> {
+        let value = f();
 
-        // If this was the last slot after Boxed64 was previously dropped, then the
-        // underlying heap allocation needs to be dropped
-        if occupancy.eq(&!(1 << self.idx)) {
-            unsafe {
-                drop(Box::from_raw(self.slab as *mut Inner<T>));
-            }
+        // Writing doesn't drop the uninitialized contents
+        unsafe { self.cell.as_ptr().write(value) };
+
+        unsafe { self.assume_init() }
+    }
+
+    /// Initialize slot by zeroing the cell in place, without constructing a
+    /// value on the stack first
+    #[cfg(feature = "bytemuck")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+    pub fn insert_zeroed(mut self) -> Slot<T>
+    where
+        T: bytemuck::Zeroable,
+    {
+        unsafe {
+            self.as_mut_ptr().write_bytes(0, 1);
+            self.assume_init()
         }
+    }
 
-        forget(self);
+    /// Initialize slot with the value returned by `f`, handing back the
+    /// reservation alongside the error should it fail so that it can be
+    /// retried without releasing the index
+    pub fn try_insert_with<E>(
+        self,
+        f: impl FnOnce() -> Result<T, E>,
+    ) -> Result<Slot<T>, (UninitSlot<T>, E)> {
+        match f() {
+            Ok(value) => Ok(self.insert(value)),
+            Err(err) => Err((self, err)),
+        }
+    }
 
-        value
+    /// Consumes [`UninitSlot`], converting into a raw pointer that points to
+    /// the underlying [`Boxed64`] with the index as the tag (low bits), using
+    /// the same scheme as [`Slot::into_raw`]
+    ///
+    /// Both schemes share one representation, so nothing distinguishes a raw
+    /// reservation from a raw initialized slot: each must only be
+    /// reconstructed by its own `from_raw`, and never by
+    /// [`Slot::try_from_raw`]
+    ///
+    /// # Safety
+    ///
+    /// For the index to be released this must be converted back into
+    /// [`UninitSlot`]
+    pub fn into_raw(self) -> *mut () {
+        let uninit = ManuallyDrop::new(self);
+
+        #[cfg(debug_assertions)]
+        unsafe { &*uninit.slab() }
+            .reserved
+            .fetch_or(1 << uninit.idx, Ordering::Relaxed);
+
+        (uninit.slab() as *mut ()).map_addr(|addr| addr | uninit.idx)
     }
 
-    /// Reconstruct [`Slot`] from a tagged pointer to become the borrow-owner of
-    /// a [`Boxed64`] cell until dropped
+    /// Reconstruct [`UninitSlot`] from a tagged pointer created by
+    /// [`UninitSlot::into_raw`] to regain the reservation
+    ///
+    /// With debug assertions enabled, reconstructing a pointer more than once
+    /// panics
     ///
     /// # Safety
     ///
-    /// This pointer must have been created by [`Slot::into_raw`] and logically
-    /// passes ownership; [`Slot`] becomes the borrow-owner of the cell
+    /// This pointer must have been created by [`UninitSlot::into_raw`] and
+    /// logically passes ownership of the reservation
     pub unsafe fn from_raw(ptr: *mut ()) -> Self {
-        Self {
-            slab: &*(ptr.map_addr(|addr| addr & IDX_MASK) as *const _),
-            idx: ptr as usize & IDX,
+        let slab = ptr.map_addr(|addr| addr & IDX_MASK) as *const Inner<T>;
+        let idx = ptr.addr() & IDX;
+
+        #[cfg(debug_assertions)]
+        {
+            let reserved = (*slab).reserved.fetch_and(!(1 << idx), Ordering::Relaxed);
+
+            assert_ne!(reserved & (1 << idx), 0, "reservation reconstructed twice");
         }
+
+        Self::new(slab, idx)
     }
 
-    /// Consumes [`Slot`], converting into a raw pointer that points to the
-    /// underlying [`Boxed64`] with the index as the tag (low bits)
+    /// Views the cell as [`MaybeUninit`] so that it can be initialized in
+    /// place before promoting with [`UninitSlot::assume_init`]
+    pub fn as_uninit_mut(&mut self) -> &mut MaybeUninit<T> {
+        unsafe { &mut *self.cell.as_ptr().cast() }
+    }
+
+    /// Promotes into a [`Slot`] once the cell has been initialized through
+    /// [`UninitSlot::as_mut_ptr`] or similar, such as by
+    /// [`ptr::copy_nonoverlapping`](core::ptr::copy_nonoverlapping) or a DMA
+    /// transfer
+    ///
+    /// Dropping an [`UninitSlot`] instead releases the index without reading
+    /// or dropping the cell, so anything written is simply discarded
     ///
     /// # Safety
     ///
-    /// For drop to be called this must be converted back into [`Slot`]
-    pub fn into_raw(self) -> *mut () {
-        let slot = ManuallyDrop::new(self);
+    /// The cell must hold a fully initialized `T`, and every write must have
+    /// completed and be visible to this thread. No references or pointers
+    /// derived from [`UninitSlot::as_uninit_mut`] or
+    /// [`UninitSlot::as_mut_ptr`] may be used afterwards, as [`Slot`] assumes
+    /// exclusive access
+    pub unsafe fn assume_init(self) -> Slot<T> {
+        // Ownership of the index passes to the slot
+        let uninit = ManuallyDrop::new(self);
+
+        Slot {
+            cell: uninit.cell,
+            idx: uninit.idx,
+        }
+    }
+}
 
-        slot.slab.map_addr(|addr| addr | slot.idx) as *mut ()
+impl<const N: usize> UninitSlot<[u8; N]> {
+    /// Views the uninitialized buffer as bytes, such as for a socket read to
+    /// fill before promoting with [`UninitSlot::assume_init`]
+    pub fn as_uninit_bytes(&mut self) -> &mut [MaybeUninit<u8>] {
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr().cast::<MaybeUninit<u8>>(), N) }
     }
 }
 
-unsafe impl<T> Send for Slot<T> where T: Send {}
-unsafe impl<T> Sync for Slot<T> where T: Sync {}
+unsafe impl<T> Send for UninitSlot<T> where T: Send {}
+unsafe impl<T> Sync for UninitSlot<T> where T: Sync {}
 
-impl<T> Deref for Slot<T> {
+impl<T> Drop for UninitSlot<T> {
+    fn drop(&mut self) {
+        unsafe { Inner::release(self.slab(), self.idx) }
+    }
+}
+
+/// Holds the value taken from a [`Slot`] by [`Slot::take_guard`]
+pub struct TakeGuard<'a, T> {
+    slot: &'a mut Slot<T>,
+    value: ManuallyDrop<T>,
+}
+
+impl<T> TakeGuard<'_, T> {
+    /// Fills the slot with `value` in place of the placeholder, returning the
+    /// taken value
+    pub fn restore(self, value: T) -> T {
+        let mut guard = ManuallyDrop::new(self);
+        let taken = unsafe { ManuallyDrop::take(&mut guard.value) };
+
+        **guard.slot = value;
+
+        taken
+    }
+}
+
+impl<T> Deref for TakeGuard<'_, T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
-        unsafe { (*self.inner().slots[self.idx].get()).assume_init_ref() }
+        &self.value
     }
 }
 
-impl<T> DerefMut for Slot<T> {
+impl<T> DerefMut for TakeGuard<'_, T> {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        unsafe { (*self.inner().slots[self.idx].get()).assume_init_mut() }
+        &mut self.value
     }
 }
 
-impl<T> Drop for Slot<T> {
+impl<T> Drop for TakeGuard<'_, T> {
     fn drop(&mut self) {
-        unsafe { (*self.inner().slots[self.idx].get()).assume_init_drop() }
+        **self.slot = unsafe { ManuallyDrop::take(&mut self.value) };
+    }
+}
 
-        let occupancy = self
-            .inner()
-            .occupancy
-            .fetch_xor(1 << self.idx, Ordering::AcqRel);
+/// A shared projection into the value of a [`Slot`], created by
+/// [`Slot::map_ref`]. The slot stays borrowed, and therefore alive, for as long
+/// as the projection
+///
+/// ```compile_fail
+/// use arena64::boxed::Boxed64;
+///
+/// let slab = Boxed64::new();
+/// let slot = slab.get_uninit_slot().unwrap().insert((1, 2));
+///
+/// let first = slot.map_ref(|pair| &pair.0);
+/// slot.take();
+/// assert_eq!(*first, 1);
+/// ```
+pub struct SlotRef<'a, U: ?Sized> {
+    value: &'a U,
+}
 
-        // If this was the last slot after Boxed64 was previously dropped, then the
-        // underlying heap allocation needs to be dropped
-        if occupancy.eq(&!(1 << self.idx)) {
-            unsafe {
-                drop(Box::from_raw(self.slab as *mut Inner<T>));
-            }
-        }
+impl<U: ?Sized> Deref for SlotRef<'_, U> {
+    type Target = U;
+    fn deref(&self) -> &Self::Target {
+        self.value
     }
 }
 
-impl<T> PartialEq<T> for Slot<T>
+impl<U> Debug for SlotRef<'_, U>
 where
-    T: PartialEq<T>,
+    U: Debug + ?Sized,
 {
-    fn eq(&self, other: &T) -> bool {
-        self.deref().eq(other)
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<U> Display for SlotRef<'_, U>
+where
+    U: Display + ?Sized,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+/// A mutable projection into the value of a [`Slot`], created by
+/// [`Slot::map_mut`]. The slot stays borrowed, and therefore alive, for as long
+/// as the projection
+pub struct SlotMut<'a, U: ?Sized> {
+    value: &'a mut U,
+}
+
+impl<U: ?Sized> Deref for SlotMut<'_, U> {
+    type Target = U;
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
+
+impl<U: ?Sized> DerefMut for SlotMut<'_, U> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.value
+    }
+}
+
+impl<U> Debug for SlotMut<'_, U>
+where
+    U: Debug + ?Sized,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<U> Display for SlotMut<'_, U>
+where
+    U: Display + ?Sized,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+/// A [`Slot`] that runs a hook on its value when dropped, but not when taken,
+/// created by [`Slot::on_drop`]
+///
+/// The hook is a plain `fn` pointer so it cannot capture state; any context it
+/// needs must be reachable from the value itself. This keeps the handle at
+/// three words
+pub struct HookedSlot<T> {
+    slot: ManuallyDrop<Slot<T>>,
+    hook: fn(&mut T),
+}
+
+impl<T> HookedSlot<T> {
+    /// Removes the hook, returning the underlying [`Slot`]
+    pub fn into_slot(self) -> Slot<T> {
+        let mut hooked = ManuallyDrop::new(self);
+        unsafe { ManuallyDrop::take(&mut hooked.slot) }
+    }
+
+    /// Takes the value without running the hook
+    pub fn take(self) -> T {
+        self.into_slot().take()
+    }
+}
+
+impl<T> Deref for HookedSlot<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.slot
+    }
+}
+
+impl<T> DerefMut for HookedSlot<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.slot
+    }
+}
+
+impl<T> Debug for HookedSlot<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<T> Drop for HookedSlot<T> {
+    fn drop(&mut self) {
+        // Owned locally so the value is still dropped should the hook panic
+        let mut slot = unsafe { ManuallyDrop::take(&mut self.slot) };
+        (self.hook)(&mut slot);
+    }
+}
+
+/// Batches the release of indexes of [`GuardedSlot`]s into a single atomic
+/// operation once dropped, created by [`Boxed64::drain_guard`]
+///
+/// Indexes of dropped [`GuardedSlot`]s aren't available for reuse until the
+/// guard itself drops
+pub struct SlabDrainGuard<'a, T> {
+    slab: &'a Boxed64<T>,
+    released: Cell<u64>,
+}
+
+impl<T> SlabDrainGuard<'_, T> {
+    /// Inserts into a free index of the underlying [`Boxed64`], returning the
+    /// value back if full
+    pub fn insert(&self, value: T) -> Result<GuardedSlot<'_, T>, T> {
+        match self.slab.get_uninit_slot() {
+            Some(uninit) => Ok(self.manage(uninit.insert(value))),
+            None => Err(value),
+        }
+    }
+
+    /// Hands the release of a [`Slot`] over to this guard, or returns it back
+    /// if it belongs to another [`Boxed64`]
+    pub fn adopt(&self, slot: Slot<T>) -> Result<GuardedSlot<'_, T>, Slot<T>> {
+        if slot.slab().eq(&self.slab.inner.cast_const()) {
+            Ok(self.manage(slot))
+        } else {
+            Err(slot)
+        }
+    }
+
+    fn manage(&self, slot: Slot<T>) -> GuardedSlot<'_, T> {
+        GuardedSlot {
+            slot: ManuallyDrop::new(slot),
+            released: &self.released,
+        }
+    }
+}
+
+impl<T> Drop for SlabDrainGuard<'_, T> {
+    fn drop(&mut self) {
+        let released = self.released.get();
+
+        if released.ne(&0) {
+            unsafe { Inner::release_many(self.slab.inner, released) }
+        }
+    }
+}
+
+/// A [`Slot`] whose index is released by a [`SlabDrainGuard`] rather than on
+/// drop
+pub struct GuardedSlot<'a, T> {
+    slot: ManuallyDrop<Slot<T>>,
+    released: &'a Cell<u64>,
+}
+
+impl<T> GuardedSlot<'_, T> {
+    /// The index of this slot within [`Boxed64`]
+    pub fn index(&self) -> usize {
+        self.slot.idx
+    }
+
+    /// Takes the value, deferring the release of the index to the guard
+    pub fn take(self) -> T {
+        let mut guarded = ManuallyDrop::new(self);
+        let value = unsafe { guarded.slot.as_mut_ptr().read() };
+
+        guarded.defer_release();
+
+        value
+    }
+
+    fn defer_release(&self) {
+        self.released
+            .set(self.released.get() | (1 << self.slot.idx));
+    }
+}
+
+impl<T> Deref for GuardedSlot<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.slot
+    }
+}
+
+impl<T> DerefMut for GuardedSlot<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.slot
+    }
+}
+
+impl<T> Debug for GuardedSlot<'_, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<T> Drop for GuardedSlot<'_, T> {
+    fn drop(&mut self) {
+        // Recorded first so the index is still released should the drop panic
+        self.defer_release();
+
+        unsafe { self.slot.as_mut_ptr().drop_in_place() }
+    }
+}
+
+/// Aborts by panicking while unwinding unless forgotten
+struct AbortOnUnwind;
+
+impl Drop for AbortOnUnwind {
+    fn drop(&mut self) {
+        panic!("panicked while a Slot was vacant");
+    }
+}
+
+/// Identifies the cell owned by a [`Slot`], created by [`Slot::by_identity`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SlotIdentity {
+    addr: usize,
+    #[cfg(feature = "shared")]
+    generation: u32,
+}
+
+/// Releases an index without dropping the cell unless forgotten
+struct ReleaseGuard<T> {
+    slab: *const Inner<T>,
+    idx: usize,
+}
+
+impl<T> Drop for ReleaseGuard<T> {
+    fn drop(&mut self) {
+        unsafe { Inner::release(self.slab, self.idx) }
+    }
+}
+
+/// Provides exclusive access over an index of [`Boxed64`] until dropped
+///
+/// Every occupied index is owned by exactly one handle at a time: an index is
+/// claimed by atomically setting its occupancy bit, and only released once its
+/// handle is gone. Access through a [`Slot`] is therefore exclusive, and
+/// `&mut self` methods such as [`Slot::replace`] need no synchronization of
+/// their own
+///
+/// Slots compare directly against values, references to values and other
+/// slots. As coherence rules prevent implementing comparisons for `T` against
+/// [`Slot`], the slot goes on the left-hand side
+///
+/// ```
+/// use arena64::boxed::Boxed64;
+///
+/// let slab = Boxed64::new();
+///
+/// let one = slab.get_uninit_slot().unwrap().insert(1);
+/// let two = slab.get_uninit_slot().unwrap().insert(2);
+///
+/// assert_eq!(one, 1);
+/// assert_eq!(one, &1);
+/// assert!(one < 2 && one < &2 && one < two);
+/// assert_eq!(one.max(two), 2);
+/// ```
+///
+/// The value's address is stable: it never changes for as long as the slot is
+/// held, whether the slot is moved, the [`Boxed64`] is dropped or other
+/// indexes are claimed and released. This is part of the public contract,
+/// which [`Slot::as_non_null`] and [`Slot::raw_tag`] rely upon
+///
+/// Like [`Box`], moving a [`Slot`] never moves its value, so [`Slot`] is
+/// [`Unpin`] regardless of `T`. Pinning through a slot still holds: a
+/// `Pin<Slot<T>>`, created by [`Slot::into_pin`] or
+/// [`UninitSlot::insert_pinned`], only gives up its value when `T` is itself
+/// [`Unpin`]
+///
+/// ```compile_fail
+/// use arena64::boxed::Boxed64;
+/// use core::{marker::PhantomPinned, pin::Pin};
+///
+/// let slab = Boxed64::new();
+/// let pinned = slab.get_uninit_slot().unwrap().insert_pinned(PhantomPinned);
+///
+/// let _moved = Pin::into_inner(pinned).take();
+/// ```
+pub struct Slot<T> {
+    // Caching the cell rather than the slab saves a dependent load on deref
+    cell: NonNull<T>,
+    pub(crate) idx: usize,
+}
+
+impl<T> Slot<T> {
+    /// # Safety
+    ///
+    /// The index must be claimed and the cell initialized. The value is
+    /// dropped and the index released once dropped
+    pub(crate) unsafe fn new(slab: *const Inner<T>, idx: usize) -> Self {
+        Slot {
+            cell: Inner::cell(slab, idx),
+            idx,
+        }
+    }
+
+    pub(crate) fn slab(&self) -> *const Inner<T> {
+        Inner::slab_of(self.cell, self.idx)
+    }
+
+    fn inner(&self) -> &Inner<T> {
+        unsafe { &*self.slab() }
+    }
+
+    /// The index of this slot within [`Boxed64`]
+    pub fn index(&self) -> usize {
+        self.idx
+    }
+
+    /// The address of the underlying [`Boxed64`] allocation
+    pub fn slab_addr(&self) -> usize {
+        self.slab().addr()
+    }
+
+    /// An identifier for the cell this slot owns, suitable as a map key that
+    /// stays the same regardless of mutation
+    ///
+    /// Once this slot drops, its index can be reused and the identity will be
+    /// shared with whichever slot next owns the cell. With the `shared`
+    /// feature enabled, the identity includes the cell's generation, which
+    /// distinguishes successive owners
+    pub fn by_identity(&self) -> SlotIdentity {
+        SlotIdentity {
+            addr: self.slab().addr() | self.idx,
+            #[cfg(feature = "shared")]
+            generation: self.inner().state[self.idx].load(Ordering::Relaxed) & !crate::shared::REFS,
+        }
+    }
+
+    /// Returns a raw pointer to the value, derived directly from the
+    /// [`UnsafeCell`] without creating intermediate references. The pointer
+    /// remains valid for as long as this slot is held, making it suitable for
+    /// aliasing-sensitive uses such as FFI callbacks
+    pub fn as_ptr(&self) -> *const T {
+        self.cell.as_ptr()
+    }
+
+    /// Returns a raw mutable pointer to the value, derived directly from the
+    /// [`UnsafeCell`] without creating intermediate references. The pointer
+    /// remains valid for as long as this slot is held, making it suitable for
+    /// aliasing-sensitive uses such as FFI callbacks
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.cell.as_ptr()
+    }
+
+    /// Returns a [`NonNull`] pointer to the value, derived directly from the
+    /// [`UnsafeCell`] without creating intermediate references. As values
+    /// never move while their slot is held, this is suitable for links within
+    /// intrusive collections
+    pub fn as_non_null(&self) -> NonNull<T> {
+        self.cell
+    }
+
+    pub fn take(self) -> T {
+        let value = unsafe { self.cell.as_ptr().read() };
+
+        unsafe { Inner::release(self.slab(), self.idx) }
+
+        forget(self);
+
+        value
+    }
+
+    /// Replaces the value, returning the previous one while retaining the
+    /// index
+    pub fn replace(&mut self, value: T) -> T {
+        mem::replace(&mut **self, value)
+    }
+
+    /// Takes the value, leaving [`Default::default`] in its place
+    ///
+    /// The index stays occupied throughout, so a slot can serve as a reusable
+    /// mailbox: the owner drains it each cycle without the index ever being
+    /// released and claimed by another thread in between
+    pub fn take_default(&mut self) -> T
+    where
+        T: Default,
+    {
+        mem::take(&mut **self)
+    }
+
+    /// Whether the value equals [`Default::default`], such as an empty
+    /// mailbox drained by [`Slot::take_default`]
+    pub fn is_default(&self) -> bool
+    where
+        T: Default + PartialEq,
+    {
+        (**self).eq(&T::default())
+    }
+
+    /// Releases the index without dropping or moving the value, such as when
+    /// ownership of its contents was transferred out-of-band
+    ///
+    /// **The value's destructor never runs**, so any resources it owns are
+    /// leaked. This is safe, as with [`mem::forget`], but only intended for
+    /// values whose contents are no longer theirs to drop
+    pub fn forget_value(self) {
+        let slot = ManuallyDrop::new(self);
+
+        unsafe { Inner::release(slot.slab(), slot.idx) }
+    }
+
+    /// Takes the value alongside the index it occupied, which is free for
+    /// reuse once this returns
+    pub fn take_with_index(self) -> (usize, T) {
+        let idx = self.idx;
+        (idx, self.take())
+    }
+
+    /// Transforms the value in place, returning this slot alongside a derived
+    /// result. The index is retained throughout
+    ///
+    /// If `f` panics the index is released without dropping the cell, as the
+    /// value will have been moved into `f`
+    pub fn with_value<R>(self, f: impl FnOnce(T) -> (T, R)) -> (Slot<T>, R) {
+        let mut slot = ManuallyDrop::new(self);
+
+        let guard = ReleaseGuard {
+            slab: slot.slab(),
+            idx: slot.idx,
+        };
+
+        let (value, result) = f(unsafe { slot.as_ptr().read() });
+
+        forget(guard);
+
+        unsafe { slot.as_mut_ptr().write(value) };
+
+        (ManuallyDrop::into_inner(slot), result)
+    }
+
+    /// Maps the value in place with a fallible transform, retaining this index
+    /// either way. On failure `f` returns the original value alongside the
+    /// error so that it can be written back
+    ///
+    /// # Aborts
+    ///
+    /// The cell is vacant while `f` runs, so if `f` panics the process aborts
+    /// rather than leave this slot owning a value that has been moved out
+    pub fn try_map_in_place<E>(&mut self, f: impl FnOnce(T) -> Result<T, (T, E)>) -> Result<(), E> {
+        let bomb = AbortOnUnwind;
+
+        let (value, result) = match f(unsafe { self.as_ptr().read() }) {
+            Ok(value) => (value, Ok(())),
+            Err((value, err)) => (value, Err(err)),
+        };
+
+        forget(bomb);
+
+        unsafe { self.as_mut_ptr().write(value) };
+
+        result
+    }
+
+    /// Moves the value into `f`, releasing this index only should `f`
+    /// succeed. On failure `f` returns the original value alongside the error
+    /// so that it can be written back and this slot returned
+    ///
+    /// # Aborts
+    ///
+    /// The cell is vacant while `f` runs, so if `f` panics the process aborts
+    /// rather than leave this slot owning a value that has been moved out
+    pub(crate) fn try_take_with<U, E>(
+        self,
+        f: impl FnOnce(T) -> Result<U, (T, E)>,
+    ) -> Result<U, (Self, E)> {
+        let bomb = AbortOnUnwind;
+
+        let result = f(unsafe { self.as_ptr().read() });
+
+        forget(bomb);
+
+        match result {
+            Ok(value) => {
+                unsafe { Inner::release(self.slab(), self.idx) }
+
+                forget(self);
+
+                Ok(value)
+            }
+            Err((value, err)) => {
+                let mut slot = self;
+                unsafe { slot.as_mut_ptr().write(value) };
+
+                Err((slot, err))
+            }
+        }
+    }
+
+    /// Projects into part of the value, such as a field, keeping this slot
+    /// borrowed for as long as the projection lives
+    pub fn map_ref<U: ?Sized>(&self, f: impl FnOnce(&T) -> &U) -> SlotRef<'_, U> {
+        SlotRef { value: f(self) }
+    }
+
+    /// Mutably projects into part of the value, such as a field, keeping this
+    /// slot borrowed for as long as the projection lives
+    pub fn map_mut<U: ?Sized>(&mut self, f: impl FnOnce(&mut T) -> &mut U) -> SlotMut<'_, U> {
+        SlotMut { value: f(self) }
+    }
+
+    /// Pins the value in place, as with [`Box::into_pin`]
+    pub fn into_pin(self) -> Pin<Self> {
+        // The value never moves while the slot is held, and a forgotten slot
+        // never releases its index, so the cell is never reused without the
+        // value being dropped
+        unsafe { Pin::new_unchecked(self) }
+    }
+
+    /// Calls `f` with a reference to the value, returning this slot so that
+    /// calls can be chained
+    pub fn inspect(self, f: impl FnOnce(&T)) -> Self {
+        f(&self);
+        self
+    }
+
+    /// Calls `f` with a mutable reference to the value, returning this slot
+    /// so that calls can be chained
+    pub fn inspect_mut(mut self, f: impl FnOnce(&mut T)) -> Self {
+        f(&mut self);
+        self
+    }
+
+    /// Attaches a hook to run on the value right before it is dropped, such as
+    /// to distinguish cancellation from completion. The hook does not run
+    /// when the value is taken via [`HookedSlot::take`]
+    pub fn on_drop(self, hook: fn(&mut T)) -> HookedSlot<T> {
+        HookedSlot {
+            slot: ManuallyDrop::new(self),
+            hook,
+        }
+    }
+
+    /// Moves the value out into a [`TakeGuard`] while retaining this index,
+    /// leaving `T::default()` in the cell as a placeholder until the guard is
+    /// resolved
+    ///
+    /// [`TakeGuard::restore`] replaces the placeholder and returns the taken
+    /// value. If the guard is instead dropped, including while unwinding from
+    /// a panic, the taken value (along with any changes made through the
+    /// guard) is written back. Should the guard be leaked, the placeholder
+    /// remains and the taken value is leaked with it
+    pub fn take_guard(&mut self) -> TakeGuard<'_, T>
+    where
+        T: Default,
+    {
+        let value = mem::take(&mut **self);
+
+        TakeGuard {
+            slot: self,
+            value: ManuallyDrop::new(value),
+        }
+    }
+
+    /// Moves the value out into a new [`Box`], releasing this index
+    ///
+    /// The layouts differ, so this copies the value into a fresh heap
+    /// allocation rather than reusing the slab
+    pub fn into_box(self) -> Box<T> {
+        Box::new(self.take())
+    }
+
+    /// Reconstruct [`Slot`] from a tagged pointer to become the borrow-owner of
+    /// a [`Boxed64`] cell until dropped
+    ///
+    /// # Safety
+    ///
+    /// This pointer must have been created by [`Slot::into_raw`] and logically
+    /// passes ownership; [`Slot`] becomes the borrow-owner of the cell
+    pub unsafe fn from_raw(ptr: *mut ()) -> Self {
+        Self::from_raw_parts(ptr.map_addr(|addr| addr & IDX_MASK), ptr.addr() & IDX)
+    }
+
+    /// Reconstruct [`Slot`] from the slab pointer and index returned by
+    /// [`Slot::into_raw_parts`] to become the borrow-owner of a [`Boxed64`]
+    /// cell until dropped
+    ///
+    /// # Safety
+    ///
+    /// These parts must have been created by [`Slot::into_raw_parts`] and
+    /// logically pass ownership; [`Slot`] becomes the borrow-owner of the cell
+    pub unsafe fn from_raw_parts(slab: *mut (), idx: usize) -> Self {
+        Self::new(slab as *const Inner<T>, idx)
+    }
+
+    /// Reconstruct [`Slot`] from a tagged pointer created by
+    /// [`Slot::into_raw_checked`], unless it has already been reconstructed
+    ///
+    /// Reconstructing atomically claims the index, so of any number of threads
+    /// racing to reconstruct the same pointer exactly one succeeds. This
+    /// guards against reconstructing a pointer twice, such as from both a
+    /// callback and a timeout path
+    ///
+    /// It does not guard against reconstructing a pointer after its index has
+    /// been released and converted into a raw pointer anew: the stale pointer
+    /// would then claim the new value
+    ///
+    /// Only [`Slot::into_raw_checked`] and this record reconstruction, so
+    /// mixing either with [`Slot::into_raw`], [`Slot::from_raw`] or
+    /// [`Slot::raw_tag`] on the same index is unsupported
+    ///
+    /// # Safety
+    ///
+    /// This pointer must have been created by [`Slot::into_raw_checked`], not
+    /// by [`UninitSlot::into_raw`], and the underlying [`Boxed64`] allocation
+    /// must still be live. As the last
+    /// slot of a dropped [`Boxed64`] deallocates it, the slab must be kept
+    /// alive by other means, such as by holding [`Boxed64`], for as long as
+    /// this may be called
+    pub unsafe fn try_from_raw(ptr: *mut ()) -> Option<Self> {
+        let slab = ptr.map_addr(|addr| addr & IDX_MASK) as *const Inner<T>;
+        let idx = ptr.addr() & IDX;
+
+        let raw = (*slab).raw.fetch_and(!(1 << idx), Ordering::AcqRel);
+
+        (raw & (1 << idx)).ne(&0).then(|| Self::new(slab, idx))
+    }
+
+    /// Consumes [`Slot`], converting into a raw pointer that points to the
+    /// underlying [`Boxed64`] with the index as the tag (low bits)
+    ///
+    /// # Safety
+    ///
+    /// For drop to be called this must be converted back into [`Slot`]
+    pub fn into_raw(self) -> *mut () {
+        let (slab, idx) = self.into_raw_parts();
+
+        slab.map_addr(|addr| addr | idx)
+    }
+
+    /// Consumes [`Slot`], converting into a tagged pointer as with
+    /// [`Slot::into_raw`] that can only be reconstructed by
+    /// [`Slot::try_from_raw`], which this records the pointer for
+    ///
+    /// # Safety
+    ///
+    /// For drop to be called this must be converted back into [`Slot`] by
+    /// [`Slot::try_from_raw`]
+    pub fn into_raw_checked(self) -> *mut () {
+        let slot = ManuallyDrop::new(self);
+
+        slot.inner().raw.fetch_or(1 << slot.idx, Ordering::Release);
+
+        slot.raw_tag()
+    }
+
+    /// Consumes [`Slot`], converting into a tagged pointer as with
+    /// [`Slot::into_raw`], typed to express that it is never null so that
+    /// `Option<NonNull<()>>` stays pointer-sized
+    ///
+    /// # Safety
+    ///
+    /// For drop to be called this must be converted back into [`Slot`]
+    pub fn into_raw_non_null(self) -> NonNull<()> {
+        unsafe { NonNull::new_unchecked(self.into_raw()) }
+    }
+
+    /// Reconstruct [`Slot`] from a tagged pointer created by
+    /// [`Slot::into_raw_non_null`]
+    ///
+    /// # Safety
+    ///
+    /// This pointer must have been created by [`Slot::into_raw_non_null`] or
+    /// [`Slot::into_raw`] and logically passes ownership; [`Slot`] becomes the
+    /// borrow-owner of the cell
+    pub unsafe fn from_raw_non_null(ptr: NonNull<()>) -> Self {
+        Self::from_raw(ptr.as_ptr())
+    }
+
+    /// The tagged pointer [`Slot::into_raw`] would return, without giving up
+    /// ownership. Slots never move their values, making this suitable as a
+    /// link between values, such as in an intrusive list
+    ///
+    /// A link can be followed by reconstructing the [`Slot`] within
+    /// [`ManuallyDrop`] so that it borrows rather than owns the cell. The
+    /// linked slot must outlive every such borrow, so unlink before dropping.
+    /// Links are not recorded, so they must not be mixed with
+    /// [`Slot::into_raw_checked`] and [`Slot::try_from_raw`] on the same index
+    ///
+    /// ```
+    /// use arena64::boxed::{Boxed64, Slot};
+    /// use core::{
+    ///     mem::ManuallyDrop,
+    ///     ptr,
+    ///     sync::atomic::{AtomicPtr, Ordering},
+    /// };
+    ///
+    /// struct Node {
+    ///     value: usize,
+    ///     next: AtomicPtr<()>,
+    /// }
+    ///
+    /// let slab = Boxed64::new();
+    /// let node = |value| Node { value, next: AtomicPtr::new(ptr::null_mut()) };
+    ///
+    /// let head = slab.get_uninit_slot().unwrap().insert(node(1));
+    /// let tail = slab.get_uninit_slot().unwrap().insert(node(2));
+    ///
+    /// head.next.store(tail.raw_tag(), Ordering::Release);
+    ///
+    /// let next = ManuallyDrop::new(unsafe { Slot::<Node>::from_raw(head.next.load(Ordering::Acquire)) });
+    /// assert_eq!(next.value, 2);
+    ///
+    /// head.next.store(ptr::null_mut(), Ordering::Release);
+    /// drop(tail);
+    /// ```
+    pub fn raw_tag(&self) -> *mut () {
+        (self.slab() as *mut ()).map_addr(|addr| addr | self.idx)
+    }
+
+    /// Consumes [`Slot`], returning the untagged pointer to the underlying
+    /// [`Boxed64`] alongside the index
+    ///
+    /// Unlike [`Slot::into_raw`], the index isn't stored in the low bits of
+    /// the pointer, leaving callers free to pack both however they see fit.
+    /// The slab pointer is always 64-byte aligned, so its low 6 bits are free
+    /// for the caller's own tags, and the index always fits within 6 bits.
+    /// Parts and tagged pointers are interchangeable: either form may be
+    /// reconstructed and converted into the other
+    ///
+    /// # Safety
+    ///
+    /// For drop to be called this must be converted back into [`Slot`]
+    pub fn into_raw_parts(self) -> (*mut (), usize) {
+        let slot = ManuallyDrop::new(self);
+
+        (slot.slab() as *mut (), slot.idx)
+    }
+
+    /// Reinterprets the value in place as `U`, such as to erase a newtype,
+    /// without moving it
+    ///
+    /// # Safety
+    ///
+    /// `T` and `U` must have the same size and alignment, and the value must
+    /// be a valid `U`. The value will be dropped as `U`. Because the slab is
+    /// `repr(C)` with a header independent of `T`, equal alignment places
+    /// the slots at the same offset, so the slab of a `Slot<U>` is found at
+    /// the same address; this is checked at compile time
+    pub unsafe fn cast<U>(self) -> Slot<U> {
+        const {
+            assert!(mem::offset_of!(Inner<T>, slots) == mem::offset_of!(Inner<U>, slots));
+        }
+        debug_assert_eq!(mem::size_of::<T>(), mem::size_of::<U>());
+        debug_assert_eq!(mem::align_of::<T>(), mem::align_of::<U>());
+
+        let slot = ManuallyDrop::new(self);
+
+        Slot {
+            cell: slot.cell.cast(),
+            idx: slot.idx,
+        }
+    }
+
+    /// Consumes and leaks [`Slot`], returning a reference to the value that
+    /// lives for as long as the caller chooses
+    ///
+    /// Neither the value nor the underlying [`Boxed64`] allocation will ever be
+    /// dropped as the index is never released. This is primarily useful for
+    /// values computed at runtime that live for the remainder of the program
+    pub fn leak<'a>(self) -> &'a T
+    where
+        T: 'a,
+    {
+        Self::leak_mut(self)
+    }
+
+    /// Consumes and leaks [`Slot`], returning a mutable reference to the value
+    /// that lives for as long as the caller chooses
+    ///
+    /// Neither the value nor the underlying [`Boxed64`] allocation will ever be
+    /// dropped as the index is never released
+    pub fn leak_mut<'a>(self) -> &'a mut T
+    where
+        T: 'a,
+    {
+        let slot = ManuallyDrop::new(self);
+
+        unsafe { &mut *slot.cell.as_ptr() }
+    }
+}
+
+unsafe impl<T> Send for Slot<T> where T: Send {}
+unsafe impl<T> Sync for Slot<T> where T: Sync {}
+
+impl<T> Slot<Option<T>> {
+    /// Takes the value out if present, leaving [`None`] while retaining the
+    /// index
+    ///
+    /// The slab tracks ownership of the index and the [`Option`] tracks the
+    /// presence of a value, forming a mailbox. Only `&mut` variants are
+    /// offered as the [`Option`] itself is unsynchronized; to fill and drain
+    /// from multiple threads, hand the slot between them, or hold a
+    /// synchronized value such as a `Mutex<Option<T>>` instead
+    pub fn transpose_take(&mut self) -> Option<T> {
+        (**self).take()
+    }
+
+    /// Inserts `value`, dropping any previous value, and returns a mutable
+    /// reference to it as with [`Option::insert`]
+    pub fn insert_value(&mut self, value: T) -> &mut T {
+        (**self).insert(value)
+    }
+
+    /// Whether no value is present
+    pub fn is_vacant(&self) -> bool {
+        (**self).is_none()
+    }
+
+    /// Whether a value is present
+    pub fn is_occupied(&self) -> bool {
+        (**self).is_some()
+    }
+}
+
+impl<T> Deref for Slot<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.cell.as_ref() }
+    }
+}
+
+impl<T> DerefMut for Slot<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.cell.as_mut() }
+    }
+}
+
+impl<T> Drop for Slot<T> {
+    fn drop(&mut self) {
+        unsafe { self.cell.as_ptr().drop_in_place() }
+
+        unsafe { Inner::release(self.slab(), self.idx) }
+    }
+}
+
+impl<T> PartialEq<T> for Slot<T>
+where
+    T: PartialEq<T>,
+{
+    fn eq(&self, other: &T) -> bool {
+        self.deref().eq(other)
+    }
+}
+
+impl<T> PartialEq<Slot<T>> for Slot<T>
+where
+    T: PartialEq<T>,
+{
+    fn eq(&self, other: &Slot<T>) -> bool {
+        self.deref().eq(other)
+    }
+}
+
+impl<T> PartialEq<&T> for Slot<T>
+where
+    T: PartialEq<T>,
+{
+    fn eq(&self, other: &&T) -> bool {
+        self.deref().eq(*other)
+    }
+}
+
+impl<T> Eq for Slot<T> where T: PartialEq<T> {}
+
+impl<T> PartialOrd<T> for Slot<T>
+where
+    T: PartialOrd<T>,
+{
+    fn partial_cmp(&self, other: &T) -> Option<cmp::Ordering> {
+        self.deref().partial_cmp(other)
+    }
+}
+
+impl<T> PartialOrd<&T> for Slot<T>
+where
+    T: PartialOrd<T>,
+{
+    fn partial_cmp(&self, other: &&T) -> Option<cmp::Ordering> {
+        self.deref().partial_cmp(*other)
+    }
+}
+
+impl<T> PartialOrd<Slot<T>> for Slot<T>
+where
+    T: PartialOrd<T>,
+{
+    fn partial_cmp(&self, other: &Slot<T>) -> Option<cmp::Ordering> {
+        self.deref().partial_cmp(other)
+    }
+}
+
+impl<T> Ord for Slot<T>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.deref().cmp(other)
+    }
+}
+
+impl<T> Debug for Slot<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<T, I> Index<I> for Slot<T>
+where
+    T: Index<I>,
+{
+    type Output = T::Output;
+    fn index(&self, index: I) -> &Self::Output {
+        self.deref().index(index)
+    }
+}
+
+impl<T, I> IndexMut<I> for Slot<T>
+where
+    T: IndexMut<I>,
+{
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        self.deref_mut().index_mut(index)
+    }
+}
+
+impl<T> Hash for Slot<T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Slot<[u8; N]> {
+    fn as_ref(&self) -> &[u8] {
+        self.deref()
+    }
+}
+
+impl<const N: usize> AsMut<[u8]> for Slot<[u8; N]> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.deref_mut()
+    }
+}
+
+impl Borrow<str> for Slot<String> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl AsRef<str> for Slot<String> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<U> Borrow<[U]> for Slot<Vec<U>> {
+    fn borrow(&self) -> &[U] {
+        self
+    }
+}
+
+impl<U> AsRef<[U]> for Slot<Vec<U>> {
+    fn as_ref(&self) -> &[U] {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl Borrow<std::path::Path> for Slot<std::path::PathBuf> {
+    fn borrow(&self) -> &std::path::Path {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<std::path::Path> for Slot<std::path::PathBuf> {
+    fn as_ref(&self) -> &std::path::Path {
+        self
+    }
+}
+
+impl<T> Display for Slot<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<T> Error for Slot<T>
+where
+    T: Error,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.deref().source()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        boxed::Box,
+        string::{String, ToString},
+        sync::Arc,
+        vec::Vec,
+    };
+    use core::{
+        cell::Cell,
+        error::Error,
+        fmt::{self, Display},
+        marker::PhantomPinned,
+        mem,
+        panic::AssertUnwindSafe,
+        pin::Pin,
+        ptr::{self, NonNull},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+    use std::{collections::HashMap, sync::mpsc, thread};
+
+    use super::{Boxed64, BoxedOwned64, Inner, Slot, SlotIdentity, UninitSlot};
+    use crate::backoff::SpinBackoff;
+
+    #[test]
+    fn fixed64_allocs_64() {
+        let slab = Boxed64::new();
+
+        let slots: Vec<UninitSlot<usize>> =
+            (0..64).filter_map(|_| slab.get_uninit_slot()).collect();
+
+        assert_eq!(slots.len(), 64);
+        assert!(slab.get_uninit_slot().is_none());
+
+        let slots: Vec<Slot<usize>> = slots
+            .into_iter()
+            .enumerate()
+            .map(|(i, slot)| slot.insert(i))
+            .collect();
+
+        assert_eq!(slots, (0..64).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn fixed64_converts_into_and_from_raw_pointer() {
+        let slab = Boxed64::new();
+
+        let slots: Vec<UninitSlot<usize>> =
+            (0..64).filter_map(|_| slab.get_uninit_slot()).collect();
+
+        assert_eq!(slots.len(), 64);
+        assert!(slab.get_uninit_slot().is_none());
+
+        let slots: Vec<Slot<usize>> = slots
+            .into_iter()
+            .enumerate()
+            .map(|(i, slot)| slot.insert(i))
+            .collect();
+
+        let pointers: Vec<*mut ()> = slots.into_iter().map(|slot| slot.into_raw()).collect();
+
+        let slots: Vec<Slot<usize>> = pointers
+            .into_iter()
+            .map(|ptr| unsafe { Slot::from_raw(ptr) })
+            .collect();
+
+        assert_eq!(slab.inner().occupancy.load(Ordering::Acquire), u64::MAX);
+        assert_eq!(slots, (0..64).collect::<Vec<usize>>());
+
+        drop(slots);
+
+        assert_eq!(slab.inner().occupancy.load(Ordering::Acquire), 0);
+    }
+
+    #[test]
+    fn drops_after_last_slot() {
+        let slab = Boxed64::new();
+
+        let slots: Vec<UninitSlot<usize>> =
+            (0..64).filter_map(|_| slab.get_uninit_slot()).collect();
+
+        assert_eq!(slots.len(), 64);
+        assert!(slab.get_uninit_slot().is_none());
+
+        let slots: Vec<Slot<usize>> = slots
+            .into_iter()
+            .enumerate()
+            .map(|(i, slot)| slot.insert(i))
+            .collect();
+
+        assert_eq!(slab.inner().occupancy.load(Ordering::Acquire), u64::MAX);
+
+        drop(slab);
+
+        assert_eq!(slots, (0..64).collect::<Vec<usize>>());
+        drop(slots);
+    }
+
+    #[test]
+    fn drops_after_last_uninit_slot() {
+        let slab = Boxed64::<usize>::new();
+
+        let uninit = slab.get_uninit_slot().unwrap();
+
+        drop(slab);
+        drop(uninit);
+    }
+
+    #[test]
+    fn inserts_after_retired() {
+        let slab = Boxed64::new();
+
+        let first = slab.get_uninit_slot().unwrap();
+        let second = slab.get_uninit_slot().unwrap();
+
+        drop(slab);
+
+        let slot = first.insert(String::from("retired"));
+
+        assert_eq!(*slot, "retired");
+
+        drop(second);
+        drop(slot);
+    }
+
+    #[test]
+    fn insert_preserves_index() {
+        let slab = Boxed64::new();
+
+        let reserved: Vec<UninitSlot<usize>> =
+            (0..64).filter_map(|_| slab.get_uninit_slot()).collect();
+
+        for (i, uninit_slot) in reserved.into_iter().enumerate() {
+            assert_eq!(uninit_slot.index(), i);
+
+            let slot = uninit_slot.insert(i);
+
+            assert_eq!(slot.index(), i);
+            assert_eq!(slot, i);
+        }
+    }
+
+    #[test]
+    fn leaked_slots_stay_occupied() {
+        let slab = Boxed64::new();
+
+        let value: &'static usize = slab.get_uninit_slot().unwrap().insert(1).leak();
+        let counter: &'static mut usize = slab.get_uninit_slot().unwrap().insert(2).leak_mut();
+
+        *counter += 1;
+
+        assert_eq!(*value, 1);
+        assert_eq!(*counter, 3);
+        assert_eq!(slab.inner().occupancy.load(Ordering::Acquire), 0b11);
+
+        drop(slab);
+
+        assert_eq!(*value, 1);
+        assert_eq!(*counter, 3);
+    }
+
+    #[test]
+    fn indexes_into_value() {
+        let slab = Boxed64::new();
+
+        let mut slot = slab.get_uninit_slot().unwrap().insert([0u8; 32]);
+
+        slot[5] = 1;
+        slot[6..8].copy_from_slice(&[2, 3]);
+
+        assert_eq!((slot[5], &slot[5..8]), (1, &[1, 2, 3][..]));
+    }
+
+    #[test]
+    fn claims_every_index_under_contention() {
+        let slab = Boxed64::<usize>::new();
+
+        let slots: Vec<UninitSlot<usize>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    scope.spawn(|| {
+                        (0..8)
+                            .map(|_| {
+                                slab.get_uninit_slot_with_backoff(&mut SpinBackoff::new())
+                                    .unwrap()
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        let mut indexes: Vec<usize> = slots.iter().map(UninitSlot::index).collect();
+        indexes.sort_unstable();
+
+        assert_eq!(indexes, (0..64).collect::<Vec<usize>>());
+        assert!(slab.get_uninit_slot().is_none());
+    }
+
+    #[test]
+    fn occupied_indexes_are_never_shared() {
+        let slab = Boxed64::<usize>::new();
+
+        std::thread::scope(|scope| {
+            for id in 0..8 {
+                let slab = &slab;
+
+                scope.spawn(move || {
+                    for round in 0..1000 {
+                        let token = id * 1000 + round;
+
+                        let mut slots: Vec<Slot<usize>> = (0..8)
+                            .map(|_| {
+                                slab.get_uninit_slot_with_backoff(&mut SpinBackoff::new())
+                                    .unwrap()
+                                    .insert(token)
+                            })
+                            .collect();
+
+                        std::thread::yield_now();
+
+                        // Another handle to the same index would have overwritten the token
+                        for slot in slots.iter_mut() {
+                            assert_eq!(slot.replace(token + 1), token);
+                        }
+                    }
+                });
+            }
+        });
+
+        assert!(slab.free_indices().eq(0..64));
+    }
+
+    #[test]
+    fn iterates_free_indices() {
+        let slab = Boxed64::new();
+
+        let slots: Vec<UninitSlot<usize>> =
+            (0..64).filter_map(|_| slab.get_uninit_slot()).collect();
+
+        assert_eq!(slab.free_indices().count(), 0);
+
+        let slots: Vec<UninitSlot<usize>> = slots
+            .into_iter()
+            .filter(|slot| slot.index() % 3 != 0)
+            .collect();
+
+        assert!(slab.free_indices().eq((0..64).filter(|idx| idx % 3 == 0)));
+
+        drop(slots);
+
+        assert!(slab.free_indices().eq(0..64));
+    }
+
+    #[test]
+    fn views_full_slab_as_slice() {
+        let slab = Boxed64::new();
+
+        let mut slots: Vec<Slot<usize>> = (0..63)
+            .map(|i| slab.get_uninit_slot().unwrap().insert(i))
+            .collect();
+
+        assert!(unsafe { slab.as_slice() }.is_none());
+
+        slots.push(slab.get_uninit_slot().unwrap().insert(63));
+
+        let values = unsafe { slab.as_slice() }.unwrap();
+
+        assert!(values.iter().copied().eq(0..64));
+        assert_eq!(values.iter().sum::<usize>(), 2016);
+
+        drop(slots);
+    }
+
+    #[test]
+    fn converts_into_and_from_raw_parts() {
+        let slab = Boxed64::new();
+
+        let slots: Vec<Slot<usize>> = (0..64)
+            .map(|i| slab.get_uninit_slot().unwrap().insert(i))
+            .collect();
+
+        let parts: Vec<(*mut (), usize)> = slots
+            .into_iter()
+            .map(|slot| slot.into_raw_parts())
+            .collect();
+
+        assert!(parts.iter().map(|(_, idx)| *idx).eq(0..64));
+
+        // Parts and tagged pointers are interchangeable
+        let pointers: Vec<*mut ()> = parts
+            .into_iter()
+            .map(|(slab, idx)| unsafe { Slot::<usize>::from_raw_parts(slab, idx) }.into_raw())
+            .collect();
+
+        let slots: Vec<Slot<usize>> = pointers
+            .into_iter()
+            .map(|ptr| {
+                let (slab, idx) = unsafe { Slot::<usize>::from_raw(ptr) }.into_raw_parts();
+                unsafe { Slot::from_raw_parts(slab, idx) }
+            })
+            .collect();
+
+        assert_eq!(slots, (0..64).collect::<Vec<usize>>());
+        assert_eq!(slots[63].index(), 63);
+    }
+
+    #[test]
+    fn insert_never_drops_uninit_contents() {
+        struct DropCounter<'a>(&'a AtomicUsize);
+
+        impl Drop for DropCounter<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let drops = AtomicUsize::new(0);
+        let slab = Boxed64::new();
+
+        let slots: Vec<Slot<DropCounter>> = (0..64)
+            .map(|_| slab.get_uninit_slot().unwrap().insert(DropCounter(&drops)))
+            .collect();
+
+        assert_eq!(drops.load(Ordering::Relaxed), 0);
+
+        drop(slots);
+
+        assert_eq!(drops.load(Ordering::Relaxed), 64);
+
+        let slot = slab.get_uninit_slot().unwrap().insert(DropCounter(&drops));
+
+        assert_eq!(drops.load(Ordering::Relaxed), 64);
+
+        drop(slot);
+
+        assert_eq!(drops.load(Ordering::Relaxed), 65);
+    }
+
+    unsafe fn write_through(ptr: *mut usize, value: usize) {
+        ptr.write(value);
+    }
+
+    #[test]
+    fn writes_through_raw_pointers() {
+        let slab = Boxed64::new();
+
+        let mut uninit_slot = slab.get_uninit_slot().unwrap();
+        let ptr = uninit_slot.as_mut_ptr();
+
+        let mut slot = uninit_slot.insert(1);
+
+        assert_eq!(slot.as_ptr(), ptr as *const usize);
+
+        unsafe { write_through(slot.as_mut_ptr(), 2) };
+        assert_eq!(slot, 2);
+
+        let shared = &slot;
+        unsafe { write_through(shared.as_ptr() as *mut usize, 3) };
+        assert_eq!(slot, 3);
+    }
+
+    #[test]
+    fn with_value_retains_index() {
+        let slab = Boxed64::new();
+
+        let slot = slab.get_uninit_slot().unwrap().insert(1);
+        let idx = slot.index();
+
+        let (slot, previous) = slot.with_value(|count| (count + 1, count));
+
+        assert_eq!(previous, 1);
+        assert_eq!(slot, 2);
+        assert_eq!(slot.index(), idx);
+    }
+
+    #[test]
+    fn with_value_releases_index_on_panic() {
+        let slab = Boxed64::new();
+
+        let slot = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert(Arc::new(AtomicUsize::new(0)));
+
+        let value = slot.clone();
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            slot.with_value(|value| -> (Arc<AtomicUsize>, ()) {
+                value.fetch_add(1, Ordering::Relaxed);
+                panic!("constructor failed");
+            })
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(value.load(Ordering::Relaxed), 1);
+        assert_eq!(Arc::strong_count(&value), 1);
+        assert_eq!(slab.free_indices().count(), 64);
+    }
+
+    #[test]
+    fn take_guard_restores_replacement() {
+        let slab = Boxed64::new();
+
+        let mut slot = slab.get_uninit_slot().unwrap().insert(String::from("old"));
+
+        let guard = slot.take_guard();
+        assert_eq!(*guard, "old");
+
+        let taken = guard.restore(String::from("new"));
+
+        assert_eq!(taken, "old");
+        assert_eq!(*slot, "new");
+    }
+
+    #[test]
+    fn take_guard_writes_back_when_dropped() {
+        let slab = Boxed64::new();
+
+        let mut slot = slab.get_uninit_slot().unwrap().insert(String::from("a"));
+
+        slot.take_guard().push('b');
+
+        assert_eq!(*slot, "ab");
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut guard = slot.take_guard();
+            guard.push('c');
+            panic!("interrupted");
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(*slot, "abc");
+    }
+
+    #[test]
+    fn take_guard_leaves_placeholder_when_forgotten() {
+        let slab = Boxed64::new();
+
+        let mut slot = slab.get_uninit_slot().unwrap().insert(Box::new(1));
+
+        let mut guard = slot.take_guard();
+        **guard = 2;
+        mem::forget(guard);
+
+        assert_eq!(**slot, 0);
+
+        drop(slot);
+        assert_eq!(slab.free_indices().count(), 64);
+    }
+
+    #[test]
+    fn try_map_in_place_writes_back_either_way() {
+        let slab = Boxed64::new();
+
+        let mut slot = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert(String::from("draft"));
+
+        let result: Result<(), &str> = slot.try_map_in_place(|mut value| {
+            value.push_str("-published");
+            Ok(value)
+        });
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(*slot, "draft-published");
+
+        let result = slot.try_map_in_place(|value| {
+            if value.ends_with("published") {
+                Err((value, "already published"))
+            } else {
+                Ok(value)
+            }
+        });
+
+        assert_eq!(result, Err("already published"));
+        assert_eq!(*slot, "draft-published");
+        assert_eq!(slot.index(), 0);
+    }
+
+    fn count_hook(counter: &mut Arc<AtomicUsize>) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[test]
+    fn hook_fires_on_drop_but_not_take() {
+        let slab = Boxed64::new();
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        let slot = slab.get_uninit_slot().unwrap().insert(counter.clone());
+        drop(slot.on_drop(count_hook));
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+        let slot = slab.get_uninit_slot().unwrap().insert(counter.clone());
+        let taken = slot.on_drop(count_hook).take();
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+        assert_eq!(Arc::strong_count(&taken), 2);
+    }
+
+    #[test]
+    fn hook_fires_once_on_retired_slab() {
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        let slab = Boxed64::new();
+        let hooked = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert(counter.clone())
+            .on_drop(count_hook);
+        drop(slab);
+
+        drop(hooked);
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+        assert_eq!(Arc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn take_default_keeps_index_occupied() {
+        let slab = Boxed64::new();
+        let mut mailbox = slab.get_uninit_slot().unwrap().insert(Vec::new());
+
+        for cycle in 0..3 {
+            mailbox.push(cycle);
+            assert!(!mailbox.is_default());
+
+            assert_eq!(mailbox.take_default(), [cycle]);
+            assert!(mailbox.is_default());
+            assert!(slab.free_indices().eq(1..64));
+        }
+    }
+
+    #[test]
+    fn raw_tag_matches_into_raw() {
+        let slab = Boxed64::new();
+        let slot = slab.get_uninit_slot().unwrap().insert(1);
+
+        let tag = slot.raw_tag();
+        assert_eq!(slot.into_raw(), tag);
+
+        drop(unsafe { Slot::<i32>::from_raw(tag) });
+    }
+
+    #[test]
+    fn links_intrusive_list_across_slabs() {
+        struct Node {
+            value: usize,
+            prev: Cell<Option<NonNull<Node>>>,
+            next: Cell<Option<NonNull<Node>>>,
+        }
+
+        let node = |value| Node {
+            value,
+            prev: Cell::new(None),
+            next: Cell::new(None),
+        };
+
+        let first = Boxed64::new();
+        let second = Boxed64::new();
+
+        let mut nodes = Vec::new();
+
+        for value in 0..4 {
+            let slab = if value % 2 == 0 { &first } else { &second };
+            let slot = slab.get_uninit_slot().unwrap().insert(node(value));
+
+            if let Some(tail) = nodes.last() {
+                let tail: &Slot<Node> = tail;
+                tail.next.set(Some(slot.as_non_null()));
+                slot.prev.set(Some(tail.as_non_null()));
+            }
+
+            nodes.push(slot);
+        }
+
+        // Moving the slots and retiring a slab leaves every link intact
+        let nodes: Vec<Slot<Node>> = nodes.into_iter().rev().collect();
+        drop(first);
+
+        let mut forward = Vec::new();
+        let mut cursor = Some(nodes[3].as_non_null());
+
+        while let Some(node) = cursor {
+            let node = unsafe { node.as_ref() };
+            forward.push(node.value);
+            cursor = node.next.get();
+        }
+
+        assert_eq!(forward, [0, 1, 2, 3]);
+
+        let tail = unsafe { nodes[0].prev.get().unwrap().as_ref() };
+        assert_eq!(tail.value, 2);
+    }
+
+    #[test]
+    fn mailbox_passes_between_threads() {
+        let slab = Boxed64::new();
+        let mailbox = slab.get_uninit_slot().unwrap().insert(None);
+        assert!(mailbox.is_vacant());
+
+        let (to_consumer, from_producer) = mpsc::channel::<Slot<Option<usize>>>();
+        let (to_producer, from_consumer) = mpsc::channel::<Slot<Option<usize>>>();
+
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                let mut mailbox = mailbox;
+
+                for message in 0..4 {
+                    *mailbox.insert_value(message) += 1;
+                    to_consumer.send(mailbox).unwrap();
+                    mailbox = from_consumer.recv().unwrap();
+                }
+            });
+
+            scope.spawn(move || {
+                let mut received = Vec::new();
+
+                while let Ok(mut mailbox) = from_producer.recv() {
+                    assert!(mailbox.is_occupied());
+                    received.push(mailbox.transpose_take().unwrap());
+                    assert!(mailbox.is_vacant());
+                    to_producer.send(mailbox).unwrap();
+                }
+
+                assert_eq!(received, [1, 2, 3, 4]);
+            });
+        });
+
+        assert!(slab.free_indices().eq(0..64));
+    }
+
+    #[test]
+    fn casts_between_newtypes_in_place() {
+        #[repr(transparent)]
+        struct Name(String);
+
+        let slab = Boxed64::new();
+        let slot = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert(Name("arena".to_string()));
+        let addr = slot.as_ptr().addr();
+
+        let slot: Slot<String> = unsafe { slot.cast() };
+
+        assert_eq!(slot.as_ptr().addr(), addr);
+        assert_eq!(slot, "arena".to_string());
+    }
+
+    #[test]
+    fn looks_up_interned_keys_by_borrowed_form() {
+        let slab = Boxed64::new();
+
+        let mut interned: HashMap<Slot<String>, usize> = HashMap::new();
+        interned.insert(slab.get_uninit_slot().unwrap().insert("key".to_string()), 1);
+
+        assert_eq!(interned.get("key"), Some(&1));
+        assert_eq!(interned.get("missing"), None);
+
+        let bytes = Boxed64::new();
+
+        let mut by_bytes: HashMap<Slot<Vec<u8>>, usize> = HashMap::new();
+        by_bytes.insert(bytes.get_uninit_slot().unwrap().insert(b"key".to_vec()), 2);
+
+        assert_eq!(by_bytes.get(&b"key"[..]), Some(&2));
+    }
+
+    #[test]
+    fn exactly_one_reconstruction_wins() {
+        let slab = Boxed64::new();
+
+        for _ in 0..100 {
+            // Raw pointers aren't Send, so the address is shared with exposed provenance
+            let addr = slab
+                .get_uninit_slot()
+                .unwrap()
+                .insert(7)
+                .into_raw_checked()
+                .expose_provenance();
+            let wins = AtomicUsize::new(0);
+
+            thread::scope(|scope| {
+                for _ in 0..2 {
+                    scope.spawn(|| {
+                        let ptr = core::ptr::with_exposed_provenance_mut(addr);
+                        if let Some(slot) = unsafe { Slot::<i32>::try_from_raw(ptr) } {
+                            assert_eq!(slot, 7);
+                            wins.fetch_add(1, Ordering::Relaxed);
+                        }
+                    });
+                }
+            });
+
+            assert_eq!(wins.load(Ordering::Relaxed), 1);
+        }
+
+        assert!(slab.free_indices().eq(0..64));
+    }
+
+    #[test]
+    fn converts_into_and_from_non_null() {
+        assert_eq!(
+            mem::size_of::<Option<NonNull<()>>>(),
+            mem::size_of::<*mut ()>()
+        );
+
+        let slab = Boxed64::new();
+        let _first = slab.get_uninit_slot().unwrap().insert(0);
+
+        let handle = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert(1)
+            .into_raw_non_null();
+        let slot = unsafe { Slot::<i32>::from_raw_non_null(handle) };
+
+        assert_eq!(slot.index(), 1);
+        assert_eq!(slot, 1);
+    }
+
+    #[test]
+    fn forget_value_frees_index_without_dropping() {
+        struct DropCounter<'a>(&'a AtomicUsize);
+
+        impl Drop for DropCounter<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let drops = AtomicUsize::new(0);
+        let slab = Boxed64::new();
+
+        slab.get_uninit_slot()
+            .unwrap()
+            .insert(DropCounter(&drops))
+            .forget_value();
+
+        assert_eq!(drops.load(Ordering::Relaxed), 0);
+        assert!(slab.free_indices().eq(0..64));
+
+        // Retired slabs are still deallocated by the last slot
+        let slot = slab.get_uninit_slot().unwrap().insert(DropCounter(&drops));
+        drop(slab);
+        slot.forget_value();
+
+        assert_eq!(drops.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn claims_boundary_indexes() {
+        let slab = Boxed64::<usize>::new();
+
+        for (occupancy, idx) in [(u64::MAX >> 1, 63), (u64::MAX << 1, 0)] {
+            slab.inner().occupancy.store(occupancy, Ordering::Relaxed);
+
+            let uninit_slot = slab.get_uninit_slot().unwrap();
+            assert_eq!(uninit_slot.index(), idx);
+            assert!(slab.get_uninit_slot().is_none());
+
+            drop(uninit_slot);
+        }
+
+        // Clear the fabricated bits so that the slab deallocates on drop
+        slab.inner().occupancy.store(0, Ordering::Relaxed);
+    }
+
+    #[test]
+    fn projects_into_fields() {
+        struct Record {
+            name: String,
+            hits: usize,
+        }
+
+        let slab = Boxed64::new();
+        let mut slot = slab.get_uninit_slot().unwrap().insert(Record {
+            name: "arena".to_string(),
+            hits: 0,
+        });
+
+        *slot.map_mut(|record| &mut record.hits) += 2;
+
+        let name = slot.map_ref(|record| record.name.as_str());
+        assert_eq!(&*name, "arena");
+        assert_eq!(name.to_string(), "arena");
+        assert_eq!(alloc::format!("{name:?}"), "\"arena\"");
+
+        assert_eq!(slot.take().hits, 2);
+    }
+
+    #[test]
+    fn fills_byte_buffers_in_place() {
+        let slab = Boxed64::<[u8; 16]>::new();
+        let mut uninit_slot = slab.get_uninit_slot().unwrap();
+
+        // A partial fill, such as a short read, initializes only a prefix
+        let buffer = uninit_slot.as_uninit_bytes();
+        assert_eq!(buffer.len(), 16);
+
+        for (byte, value) in buffer[..4].iter_mut().zip(*b"arena") {
+            byte.write(value);
+        }
+
+        drop(uninit_slot);
+        assert!(slab.free_indices().eq(0..64));
+
+        let mut uninit_slot = slab.get_uninit_slot().unwrap();
+
+        for byte in uninit_slot.as_uninit_bytes() {
+            byte.write(7);
+        }
+
+        let mut slot = unsafe { uninit_slot.assume_init() };
+        slot.as_mut()[0] = 0;
+
+        let bytes: &[u8] = slot.as_ref();
+        assert_eq!(bytes[..2], [0, 7]);
+    }
+
+    #[test]
+    fn take_with_index_frees_index() {
+        let slab = Boxed64::new();
+        let _first = slab.get_uninit_slot().unwrap().insert(0);
+        let second = slab.get_uninit_slot().unwrap().insert(1);
+
+        assert_eq!(second.take_with_index(), (1, 1));
+        assert_eq!(slab.get_uninit_slot().unwrap().index(), 1);
     }
-}
 
-impl<T> PartialEq<Slot<T>> for Slot<T>
-where
-    T: PartialEq<T>,
-{
-    fn eq(&self, other: &Slot<T>) -> bool {
-        self.deref().eq(other)
+    #[test]
+    fn identity_survives_mutation() {
+        let slab = Boxed64::new();
+
+        let mut slots: Vec<Slot<String>> = (0..4)
+            .map(|i| slab.get_uninit_slot().unwrap().insert(i.to_string()))
+            .collect();
+
+        let registry: HashMap<SlotIdentity, usize> = slots
+            .iter()
+            .map(|slot| (slot.by_identity(), slot.index()))
+            .collect();
+
+        for slot in slots.iter_mut() {
+            slot.push_str("-mutated");
+        }
+
+        for slot in slots.iter() {
+            assert_eq!(registry.get(&slot.by_identity()), Some(&slot.index()));
+        }
+
+        assert_ne!(slots[0].by_identity(), slots[1].by_identity());
     }
-}
 
-impl<T> Eq for Slot<T> where T: PartialEq<T> {}
+    #[cfg(feature = "notify")]
+    #[test]
+    fn notifies_occupancy_transitions() {
+        use super::OccupancyHooks;
+
+        static FULL: AtomicUsize = AtomicUsize::new(0);
+        static EMPTY: AtomicUsize = AtomicUsize::new(0);
+
+        let slab = Boxed64::with_hooks(OccupancyHooks {
+            on_became_full: Some(|| {
+                FULL.fetch_add(1, Ordering::Relaxed);
+            }),
+            on_became_empty: Some(|| {
+                EMPTY.fetch_add(1, Ordering::Relaxed);
+            }),
+        });
+
+        for _ in 0..2 {
+            let mut slots: Vec<Slot<usize>> = (0..64)
+                .map(|i| slab.get_uninit_slot().unwrap().insert(i))
+                .collect();
+
+            let last = slots.pop().unwrap();
+            drop(last);
+            slots.push(slab.get_uninit_slot().unwrap().insert(63));
+
+            drop(slots);
+        }
 
-impl<T> Debug for Slot<T>
-where
-    T: Debug,
-{
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        self.deref().fmt(f)
+        assert_eq!(FULL.load(Ordering::Relaxed), 4);
+        assert_eq!(EMPTY.load(Ordering::Relaxed), 2);
+
+        // Retired slabs no longer report transitions
+        let slot = slab.get_uninit_slot().unwrap().insert(0);
+        drop(slab);
+        drop(slot);
+
+        assert_eq!(EMPTY.load(Ordering::Relaxed), 2);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use alloc::vec::Vec;
-    use core::sync::atomic::Ordering;
+    #[derive(Debug)]
+    struct RootCause;
+
+    impl Display for RootCause {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("root cause")
+        }
+    }
+
+    impl Error for RootCause {}
+
+    #[derive(Debug)]
+    struct Failure(RootCause);
 
-    use super::{Boxed64, Slot, UninitSlot};
+    impl Display for Failure {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("failure")
+        }
+    }
+
+    impl Error for Failure {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
 
     #[test]
-    fn fixed64_allocs_64() {
+    fn slot_forwards_error_source() {
         let slab = Boxed64::new();
 
-        let slots: Vec<UninitSlot<usize>> =
-            (0..64).filter_map(|_| slab.get_uninit_slot()).collect();
+        let slot = slab.get_uninit_slot().unwrap().insert(Failure(RootCause));
+        let err: &dyn Error = &slot;
+
+        assert_eq!(err.to_string(), "failure");
+
+        let source = err.source().unwrap();
+
+        assert!(source.is::<RootCause>());
+        assert_eq!(source.to_string(), "root cause");
+        assert!(source.source().is_none());
+    }
+
+    #[test]
+    fn recovers_slab_from_cell() {
+        fn recovers<T: Default>() {
+            let slab = Boxed64::<T>::new();
+
+            let slots: Vec<_> = (0..64)
+                .filter_map(|_| slab.get_uninit_slot())
+                .map(|slot| slot.insert(T::default()))
+                .collect();
+
+            assert!(slots.iter().all(|slot| ptr::eq(slot.slab(), slab.inner())));
+        }
+
+        recovers::<()>();
+        recovers::<u8>();
+        recovers::<[u64; 32]>();
+    }
+
+    #[test]
+    fn promotes_raw_writes() {
+        let slab = Boxed64::<String>::new();
+
+        let mut uninit = slab.get_uninit_slot().unwrap();
+        unsafe { uninit.as_mut_ptr().write(String::from("raw")) };
+        let raw = unsafe { uninit.assume_init() };
+
+        let mut uninit = slab.get_uninit_slot().unwrap();
+        uninit.as_uninit_mut().write(String::from("uninit"));
+        let written = unsafe { uninit.assume_init() };
+
+        assert_eq!(*raw, "raw");
+        assert_eq!(*written, "uninit");
+    }
+
+    #[test]
+    fn uninit_drop_ignores_cell() {
+        struct DropCounter<'a>(&'a AtomicUsize);
+
+        impl Drop for DropCounter<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let drops = AtomicUsize::new(0);
+        let slab = Boxed64::new();
+
+        let mut uninit = slab.get_uninit_slot().unwrap();
+        uninit.as_uninit_mut().write(DropCounter(&drops));
+        drop(uninit);
+
+        assert_eq!(drops.load(Ordering::Relaxed), 0);
+        assert_eq!(slab.inner().occupancy.load(Ordering::Acquire), 0);
+    }
+
+    #[test]
+    fn extends_until_full() {
+        let slab = Boxed64::new();
+        let mut slots = Vec::new();
+        let mut values = 0..100;
+
+        assert_eq!(slab.extend(&mut slots, values.by_ref()), 64);
+        assert_eq!(values.next(), Some(64));
+        assert!(slots.iter().map(|slot| **slot).eq(0..64));
+
+        slots.truncate(10);
+
+        assert_eq!(slab.extend(&mut slots, 0..3), 3);
+        assert_eq!(slots.len(), 13);
+    }
+
+    #[test]
+    fn extends_exactly() {
+        let slab = Boxed64::new();
+
+        let slots = slab.try_extend_exact([0; 60]).unwrap();
+        let values = slab.try_extend_exact([1, 2, 3, 4, 5]).unwrap_err();
+
+        assert_eq!(values, [1, 2, 3, 4, 5]);
+        assert_eq!(slab.free_indices().count(), 4);
+
+        drop(slots);
+
+        let slots = slab.try_extend_exact([1, 2, 3, 4, 5]).unwrap();
+
+        assert!(slots.iter().map(|slot| **slot).eq(1..=5));
+    }
+
+    #[test]
+    fn insert_with_releases_on_panic() {
+        let slab = Boxed64::<String>::new();
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            slab.get_uninit_slot()
+                .unwrap()
+                .insert_with(|| panic!("constructor failed"))
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(slab.free_indices().count(), 64);
+
+        let slot = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert_with(|| String::from("constructed"));
+
+        assert_eq!(slot.index(), 0);
+        assert_eq!(*slot, "constructed");
+    }
+
+    #[test]
+    fn drain_guard_releases_once_dropped() {
+        let slab = Boxed64::new();
+        let other = Boxed64::new();
+        let guard = slab.drain_guard();
+
+        let mut slots: Vec<_> = (0..63).map(|i| guard.insert(i).unwrap()).collect();
+
+        let adopted = guard
+            .adopt(slab.get_uninit_slot().unwrap().insert(63))
+            .unwrap();
+
+        assert!(guard.insert(64).is_err_and(|value| value.eq(&64)));
+        assert!(guard
+            .adopt(other.get_uninit_slot().unwrap().insert(0))
+            .is_err());
+
+        assert_eq!(slots.pop().unwrap().take(), 62);
+        drop(slots);
+        drop(adopted);
 
-        assert_eq!(slots.len(), 64);
         assert!(slab.get_uninit_slot().is_none());
 
-        let slots: Vec<Slot<usize>> = slots
-            .into_iter()
-            .enumerate()
-            .map(|(i, slot)| slot.insert(i))
+        drop(guard);
+
+        assert_eq!(slab.free_indices().count(), 64);
+    }
+
+    #[test]
+    fn reports_index_and_slab_addr() {
+        let slab = Boxed64::new();
+
+        let raw: Vec<_> = (0..64)
+            .map(|_| {
+                let uninit = slab.get_uninit_slot().unwrap();
+                let (idx, addr) = (uninit.index(), uninit.slab_addr());
+
+                let slot = uninit.insert(idx);
+                assert_eq!((slot.index(), slot.slab_addr()), (idx, addr));
+
+                let raw = slot.into_raw();
+                assert_eq!(raw.addr(), addr | idx);
+
+                raw
+            })
             .collect();
 
-        assert_eq!(slots, (0..64).collect::<Vec<usize>>());
+        for raw in raw {
+            let slot = unsafe { Slot::<usize>::from_raw(raw) };
+            assert_eq!(*slot, raw.addr() & 63);
+        }
     }
 
     #[test]
-    fn fixed64_converts_into_and_from_raw_pointer() {
+    fn claims_around_set_occupancy() {
         let slab = Boxed64::new();
 
-        let slots: Vec<UninitSlot<usize>> =
-            (0..64).filter_map(|_| slab.get_uninit_slot()).collect();
+        unsafe { slab.set_occupancy_for_test(!(1 << 5 | 1 << 63)) };
 
-        assert_eq!(slots.len(), 64);
+        let first = slab.get_uninit_slot().unwrap().insert(5);
+        let last = slab.get_uninit_slot().unwrap().insert(63);
+
+        assert_eq!((first.index(), last.index()), (5, 63));
         assert!(slab.get_uninit_slot().is_none());
 
-        let slots: Vec<Slot<usize>> = slots
-            .into_iter()
-            .enumerate()
-            .map(|(i, slot)| slot.insert(i))
+        drop(first);
+
+        assert_eq!(slab.occupancy_for_test(), !(1 << 5));
+
+        unsafe { slab.set_occupancy_for_test(1 << 63) };
+    }
+
+    #[test]
+    fn try_insert_with_retains_reservation() {
+        let slab = Boxed64::new();
+
+        let (uninit, err) = slab
+            .get_uninit_slot()
+            .unwrap()
+            .try_insert_with(|| "1x".parse::<usize>())
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "invalid digit found in string");
+        assert_eq!(uninit.index(), 0);
+        assert_eq!(slab.get_uninit_slot().unwrap().index(), 1);
+
+        let slot = uninit
+            .try_insert_with(|| "1".parse::<usize>())
+            .ok()
+            .unwrap();
+
+        assert_eq!((slot.index(), *slot), (0, 1));
+
+        let (uninit, _) = slab
+            .get_uninit_slot()
+            .unwrap()
+            .try_insert_with(|| Err::<usize, _>(()))
+            .unwrap_err();
+
+        drop(uninit);
+
+        assert_eq!(slab.free_indices().count(), 63);
+    }
+
+    #[test]
+    fn counts_occupancy() {
+        let slab = Boxed64::new();
+
+        assert!(slab.is_empty());
+
+        let mut slots: Vec<_> = (0..64)
+            .map(|i| slab.get_uninit_slot().unwrap().insert(i))
             .collect();
 
-        let pointers: Vec<*mut ()> = slots.into_iter().map(|slot| slot.into_raw()).collect();
+        assert!(slab.is_full());
 
-        let slots: Vec<Slot<usize>> = pointers
-            .into_iter()
-            .map(|ptr| unsafe { Slot::from_raw(ptr) })
+        slots.truncate(40);
+
+        assert_eq!((slab.occupied_count(), slab.available_count()), (40, 24));
+        assert!(!slab.is_empty() && !slab.is_full());
+    }
+
+    #[test]
+    fn owned_slots_borrow_slab() {
+        let slab = BoxedOwned64::new();
+
+        let slots: Vec<_> = (0..64)
+            .map(|i| {
+                slab.get_uninit_slot()
+                    .unwrap()
+                    .insert(String::from(["a", "b"][i % 2]))
+            })
             .collect();
 
-        assert_eq!(slab.inner().occupancy.load(Ordering::Acquire), u64::MAX);
-        assert_eq!(slots, (0..64).collect::<Vec<usize>>());
+        assert!(slab.get_uninit_slot().is_none());
+        assert_eq!(
+            slots.iter().filter(|slot| slot.as_str().eq("a")).count(),
+            32
+        );
 
         drop(slots);
 
-        assert_eq!(slab.inner().occupancy.load(Ordering::Acquire), 0);
+        assert_eq!(slab.free_indices().count(), 64);
     }
 
     #[test]
-    fn drops_after_last_slot() {
-        let slab = Boxed64::new();
+    fn uninit_raw_round_trip() {
+        let slab = Boxed64::<usize>::new();
 
-        let slots: Vec<UninitSlot<usize>> =
-            (0..64).filter_map(|_| slab.get_uninit_slot()).collect();
+        let raw: Vec<*mut ()> = (0..64)
+            .map(|_| slab.get_uninit_slot().unwrap().into_raw())
+            .collect();
 
-        assert_eq!(slots.len(), 64);
         assert!(slab.get_uninit_slot().is_none());
 
-        let slots: Vec<Slot<usize>> = slots
+        let slots: Vec<Slot<usize>> = raw
             .into_iter()
             .enumerate()
-            .map(|(i, slot)| slot.insert(i))
+            .map(|(i, raw)| {
+                let uninit = unsafe { UninitSlot::from_raw(raw) };
+                assert_eq!(uninit.index(), i);
+                uninit.insert(i)
+            })
             .collect();
 
-        assert_eq!(slab.inner().occupancy.load(Ordering::Acquire), u64::MAX);
-
-        drop(slab);
-
         assert_eq!(slots, (0..64).collect::<Vec<usize>>());
+
         drop(slots);
+
+        assert_eq!(slab.free_indices().count(), 64);
+    }
+
+    #[test]
+    fn raw_reservation_is_not_claimable_by_try_from_raw() {
+        let slab = Boxed64::<usize>::new();
+
+        let raw = slab.get_uninit_slot().unwrap().into_raw();
+
+        assert_eq!(slab.inner().raw.load(Ordering::Relaxed), 0);
+
+        drop(unsafe { UninitSlot::<usize>::from_raw(raw) });
+        assert!(slab.free_indices().eq(0..64));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "reservation reconstructed twice")]
+    fn uninit_from_raw_twice_panics() {
+        let slab = Boxed64::<usize>::new();
+
+        let raw = slab.get_uninit_slot().unwrap().into_raw();
+
+        let _uninit = unsafe { UninitSlot::<usize>::from_raw(raw) };
+        let _twice = unsafe { UninitSlot::<usize>::from_raw(raw) };
+    }
+
+    #[test]
+    fn inspects_in_chain() {
+        let slab = Boxed64::new();
+        let mut seen = 0;
+
+        let slot = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert(1)
+            .inspect(|value| seen = *value)
+            .inspect_mut(|value| *value += 1);
+
+        assert_eq!((seen, *slot), (1, 2));
+    }
+
+    #[test]
+    #[cfg(feature = "bytemuck")]
+    fn inserts_zeroed_in_place() {
+        #[repr(C)]
+        struct Padded {
+            tag: u8,
+            value: u64,
+        }
+
+        unsafe impl bytemuck::Zeroable for Padded {}
+
+        let slab = Boxed64::new();
+
+        let mut dirty = slab.get_uninit_slot().unwrap().insert(Padded {
+            tag: u8::MAX,
+            value: u64::MAX,
+        });
+
+        dirty.tag = 1;
+        drop(dirty);
+
+        let uninit = slab.get_uninit_slot().unwrap();
+        let cell = uninit.as_ptr();
+        let slot = uninit.insert_zeroed();
+
+        assert_eq!(slot.as_ptr(), cell);
+        assert_eq!((slot.tag, slot.value), (0, 0));
+
+        let buffer = Boxed64::<[u8; 4096]>::new()
+            .get_uninit_slot()
+            .unwrap()
+            .insert_zeroed();
+
+        assert!(buffer.iter().all(|byte| byte.eq(&0)));
+    }
+
+    #[test]
+    fn gets_unchecked() {
+        let mut slab = Boxed64::new();
+
+        let slots: Vec<_> = (0..64)
+            .map(|i| slab.get_uninit_slot().unwrap().insert(i * 2))
+            .collect();
+
+        let values: Vec<usize> = slots.iter().map(|slot| **slot).collect();
+
+        for (idx, value) in values.into_iter().enumerate() {
+            assert_eq!(unsafe { slab.get_unchecked(idx) }, &value);
+
+            unsafe { *slab.get_unchecked_mut(idx) += 1 };
+        }
+
+        assert_eq!(slots, (0..64).map(|i| i * 2 + 1).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn pins_on_insert() {
+        struct SelfRef {
+            value: u32,
+            this: *const u32,
+            _pinned: PhantomPinned,
+        }
+
+        impl SelfRef {
+            fn link(self: Pin<&mut Self>) {
+                let this = unsafe { self.get_unchecked_mut() };
+                this.this = &this.value;
+            }
+        }
+
+        let slab = Boxed64::new();
+
+        let mut pinned = slab.get_uninit_slot().unwrap().insert_pinned(SelfRef {
+            value: 7,
+            this: ptr::null(),
+            _pinned: PhantomPinned,
+        });
+
+        pinned.as_mut().link();
+
+        let moved = Vec::from([pinned]);
+
+        assert_eq!(moved[0].this, &moved[0].value as *const u32);
+        assert_eq!(unsafe { *moved[0].this }, 7);
+    }
+
+    #[cfg(feature = "async")]
+    fn poll_once<F: core::future::Future>(fut: Pin<&mut F>) -> core::task::Poll<F::Output> {
+        use alloc::{sync::Arc, task::Wake};
+        use core::task::{Context, Waker};
+
+        struct Noop;
+
+        impl Wake for Noop {
+            fn wake(self: Arc<Self>) {}
+        }
+
+        let waker = Waker::from(Arc::new(Noop));
+        fut.poll(&mut Context::from_waker(&waker))
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn inserts_future_output() {
+        let slab = Boxed64::new();
+
+        let mut fut = core::pin::pin!(slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert_future(async { String::from("ready") }));
+
+        match poll_once(fut.as_mut()) {
+            core::task::Poll::Ready(slot) => assert_eq!(*slot, "ready"),
+            core::task::Poll::Pending => panic!("future should be ready"),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn cancelling_insert_future_releases() {
+        let slab = Boxed64::<usize>::new();
+
+        let mut fut = alloc::boxed::Box::pin(
+            slab.get_uninit_slot()
+                .unwrap()
+                .insert_future(core::future::pending()),
+        );
+
+        assert!(poll_once(fut.as_mut()).is_pending());
+        assert_eq!(slab.free_indices().count(), 63);
+
+        drop(fut);
+
+        assert_eq!(slab.free_indices().count(), 64);
+    }
+
+    #[test]
+    fn claims_lowest_clear_bit() {
+        let inner = Inner::<usize>::alloc();
+        let slab = unsafe { &*inner };
+
+        let claim = |occupancy: u64| {
+            slab.occupancy.store(occupancy, Ordering::Relaxed);
+            let idx = slab.get_uninit_slot().map(|uninit| {
+                let idx = uninit.index();
+                mem::forget(uninit);
+                idx
+            });
+            (idx, slab.occupancy.load(Ordering::Relaxed))
+        };
+
+        // Empty
+        assert_eq!(claim(0), (Some(0), 1));
+        // One free
+        assert_eq!(claim(!(1 << 17)), (Some(17), u64::MAX));
+        assert_eq!(claim(!(1 << 63)), (Some(63), u64::MAX));
+        // One occupied
+        assert_eq!(claim(1), (Some(1), 0b11));
+        assert_eq!(claim(1 << 63), (Some(0), 1 << 63 | 1));
+        // Full
+        assert_eq!(claim(u64::MAX), (None, u64::MAX));
+
+        slab.occupancy.store(0, Ordering::Relaxed);
+        unsafe { Inner::retire(inner) };
     }
 }
@@ -1,13 +1,18 @@
 use core::{
     cell::UnsafeCell,
-    fmt::Debug,
+    cmp,
+    error::Error,
+    fmt::{Debug, Display},
     mem::{self, forget, ManuallyDrop, MaybeUninit},
     ops::{Deref, DerefMut},
-    ptr::addr_of,
+    ptr::{self, addr_of},
     sync::atomic::{AtomicU64, Ordering},
 };
 
-use crate::{IDX, IDX_MASK};
+use crate::{
+    backoff::{Backoff, NoBackoff},
+    free_indices, IDX, IDX_MASK,
+};
 
 /// A slab with 64 pre-allocated slots
 #[repr(align(64))]
@@ -33,15 +38,96 @@ impl<T> Fixed64<T> {
         }
     }
 
+    /// Create a [`Table64`] with every index occupied by a copy of `value`,
+    /// such as for constant lookup tables
+    pub const fn filled(value: T) -> Table64<T>
+    where
+        T: Copy,
+    {
+        Self::from_array([value; 64])
+    }
+
+    /// Create a [`Table64`] with every index occupied by the corresponding
+    /// value of `values`, such as for static tables built at compile time.
+    /// The values are never dropped
+    pub const fn from_array(values: [T; 64]) -> Table64<T> {
+        let values = ManuallyDrop::new(values);
+
+        // UnsafeCell and MaybeUninit share the layout of T
+        let slots =
+            unsafe { ptr::read(addr_of!(values).cast::<[UnsafeCell<MaybeUninit<T>>; 64]>()) };
+
+        Table64 {
+            slab: Fixed64 {
+                occupancy: AtomicU64::new(u64::MAX),
+                slots,
+            },
+        }
+    }
+
+    /// Allocate directly on the heap rather than moving through the stack
+    #[cfg(feature = "extern_crate_alloc")]
+    pub(crate) fn new_boxed() -> alloc::boxed::Box<Self> {
+        let mut slab = alloc::boxed::Box::<Self>::new_uninit();
+
+        unsafe {
+            ptr::addr_of_mut!((*slab.as_mut_ptr()).occupancy).write(AtomicU64::new(0));
+            slab.assume_init()
+        }
+    }
+
     /// Get an unoccupied [`UninitSlot`] if available
+    ///
+    /// The lowest unoccupied index is claimed, so slots are handed out in
+    /// index order while none are released. Returns [`None`] without blocking
+    /// when all 64 indexes are occupied
+    #[inline]
     pub fn get_uninit_slot(&self) -> Option<UninitSlot<'_, T>> {
-        let mut occupancy = self.occupancy.load(Ordering::Acquire);
+        self.get_uninit_slot_with_backoff(&mut NoBackoff)
+    }
+
+    /// Iterate over the indexes unoccupied at the time of calling. Other
+    /// threads may concurrently claim or release indexes, so this is only a
+    /// snapshot
+    pub fn free_indices(&self) -> impl Iterator<Item = usize> {
+        free_indices(self.occupancy.load(Ordering::Acquire))
+    }
+
+    /// Views every cell as a contiguous array when all 64 indexes are
+    /// occupied, or returns [`None`] otherwise
+    ///
+    /// # Safety
+    ///
+    /// Every index must be owned by an initialized [`Slot`] rather than an
+    /// [`UninitSlot`], and no [`Slot`] may mutate or take its value for the
+    /// lifetime of the returned reference
+    pub unsafe fn as_slice(&self) -> Option<&[T; 64]> {
+        if self.occupancy.load(Ordering::Acquire).eq(&u64::MAX) {
+            Some(&*(self.slots.as_ptr() as *const [T; 64]))
+        } else {
+            None
+        }
+    }
+
+    /// Get an unoccupied [`UninitSlot`] if available, backing off after each
+    /// lost race to claim an index
+    pub fn get_uninit_slot_with_backoff(
+        &self,
+        backoff: &mut impl Backoff,
+    ) -> Option<UninitSlot<'_, T>> {
+        // Only a hint for which index to claim. Ownership is established by the
+        // fetch_or below, whose acquire synchronizes with the release of the
+        // index by its previous owner, so no ordering is needed here; unlike
+        // consume, relaxed also avoids any barrier on weakly ordered targets
+        let mut occupancy = self.occupancy.load(Ordering::Relaxed);
 
         let idx = loop {
             // Isolate lowest clear bit. See https://docs.rs/bitintr/latest/bitintr/trait.Blcic.html
             let least_significant_bit = !occupancy & (occupancy.wrapping_add(1));
 
             if least_significant_bit.ne(&0) {
+                // Acquire synchronizes with the release of this index by its previous
+                // owner before the cell is reused
                 occupancy = self
                     .occupancy
                     .fetch_or(least_significant_bit, Ordering::AcqRel);
@@ -49,6 +135,8 @@ impl<T> Fixed64<T> {
                 if (occupancy & least_significant_bit).eq(&0) {
                     break least_significant_bit.trailing_zeros();
                 }
+
+                backoff.backoff();
             } else {
                 return None;
             }
@@ -64,6 +152,32 @@ impl<T> Fixed64<T> {
 unsafe impl<T> Send for Fixed64<T> where T: Send {}
 unsafe impl<T> Sync for Fixed64<T> where T: Sync {}
 
+/// A fully occupied [`Fixed64`] created by [`Fixed64::filled`] or
+/// [`Fixed64::from_array`]. No [`Slot`] is ever issued, so the values can be
+/// read as a `[T; 64]` without `unsafe`
+#[repr(transparent)]
+pub struct Table64<T> {
+    slab: Fixed64<T>,
+}
+
+impl<T> Deref for Table64<T> {
+    type Target = [T; 64];
+
+    fn deref(&self) -> &Self::Target {
+        // Every index is occupied and never released
+        unsafe { &*(self.slab.slots.as_ptr() as *const [T; 64]) }
+    }
+}
+
+impl<T> Debug for Table64<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
 /// Provides exclusive access over an unitialized index of [`Fixed64`] until
 /// dropped
 pub struct UninitSlot<'a, T> {
@@ -72,13 +186,94 @@ pub struct UninitSlot<'a, T> {
 }
 
 impl<'a, T> UninitSlot<'a, T> {
+    /// The index of this slot within [`Fixed64`]
+    pub fn index(&self) -> usize {
+        self.idx
+    }
+
+    /// The address of the underlying [`Fixed64`]
+    pub fn slab_addr(&self) -> usize {
+        ptr::from_ref(self.slab).addr()
+    }
+
+    /// Returns a raw pointer to the uninitialized cell, derived directly from
+    /// the [`UnsafeCell`] without creating intermediate references
+    pub fn as_ptr(&self) -> *const T {
+        self.slab.slots[self.idx].get().cast()
+    }
+
+    /// Returns a raw mutable pointer to the uninitialized cell, derived
+    /// directly from the [`UnsafeCell`] without creating intermediate
+    /// references
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.slab.slots[self.idx].get().cast()
+    }
+
     /// Initialize slot with value
     pub fn insert(self, value: T) -> Slot<'a, T> {
+        self.insert_with(|| value)
+    }
+
+    /// Holds the reservation until `fut` resolves, then initializes slot with
+    /// its output. Dropping the returned future before then, such as on
+    /// cancellation, releases the index
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub async fn insert_future(self, fut: impl core::future::Future<Output = T>) -> Slot<'a, T> {
+        let value = fut.await;
+        self.insert(value)
+    }
+
+    /// Initialize slot with the value returned by `f`, which only runs once
+    /// the index is reserved. Should `f` panic, the index is released
+    pub fn insert_with(self, f: impl FnOnce() -> T) -> Slot<'a, T> {
+        let value = f();
+
+        // MaybeUninit has no drop glue, so the uninitialized contents aren't dropped
         unsafe {
             *self.slab.slots[self.idx].get() = MaybeUninit::new(value);
         }
 
-        unsafe { mem::transmute(self) }
+        unsafe { self.assume_init() }
+    }
+
+    /// # Safety
+    ///
+    /// The cell must hold a fully initialized `T`
+    unsafe fn assume_init(self) -> Slot<'a, T> {
+        // Ownership of the index passes to the slot
+        let uninit = ManuallyDrop::new(self);
+
+        Slot {
+            slab: uninit.slab,
+            idx: uninit.idx,
+        }
+    }
+
+    /// Initialize slot by zeroing the cell in place, without constructing a
+    /// value on the stack first
+    #[cfg(feature = "bytemuck")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "bytemuck")))]
+    pub fn insert_zeroed(mut self) -> Slot<'a, T>
+    where
+        T: bytemuck::Zeroable,
+    {
+        unsafe { self.as_mut_ptr().write_bytes(0, 1) };
+
+        unsafe { self.assume_init() }
+    }
+
+    /// Initialize slot with the value returned by `f`, handing back the
+    /// reservation alongside the error should it fail so that it can be
+    /// retried without releasing the index
+    pub fn try_insert_with<E>(
+        self,
+        f: impl FnOnce() -> Result<T, E>,
+    ) -> Result<Slot<'a, T>, (UninitSlot<'a, T>, E)> {
+        match f() {
+            Ok(value) => Ok(self.insert(value)),
+            Err(err) => Err((self, err)),
+        }
     }
 }
 
@@ -94,12 +289,56 @@ impl<T> Drop for UninitSlot<'_, T> {
 }
 
 /// Provides exclusive access over an index of [`Fixed64`] until dropped
+///
+/// Slots compare directly against values, references to values and other
+/// slots. As coherence rules prevent implementing comparisons for `T` against
+/// [`Slot`], the slot goes on the left-hand side
+///
+/// ```
+/// use arena64::heapless::Fixed64;
+///
+/// let slab = Fixed64::new();
+///
+/// let one = slab.get_uninit_slot().unwrap().insert(1);
+/// let two = slab.get_uninit_slot().unwrap().insert(2);
+///
+/// assert_eq!(one, 1);
+/// assert_eq!(one, &1);
+/// assert!(one < 2 && one < &2 && one < two);
+/// assert_eq!(one.max(two), 2);
+/// ```
 pub struct Slot<'a, T> {
     slab: &'a Fixed64<T>,
     idx: usize,
 }
 
 impl<T> Slot<'_, T> {
+    /// The index of this slot within [`Fixed64`]
+    pub fn index(&self) -> usize {
+        self.idx
+    }
+
+    /// The address of the underlying [`Fixed64`]
+    pub fn slab_addr(&self) -> usize {
+        ptr::from_ref(self.slab).addr()
+    }
+
+    /// Returns a raw pointer to the value, derived directly from the
+    /// [`UnsafeCell`] without creating intermediate references. The pointer
+    /// remains valid for as long as this slot is held, making it suitable for
+    /// aliasing-sensitive uses such as FFI callbacks
+    pub fn as_ptr(&self) -> *const T {
+        self.slab.slots[self.idx].get().cast()
+    }
+
+    /// Returns a raw mutable pointer to the value, derived directly from the
+    /// [`UnsafeCell`] without creating intermediate references. The pointer
+    /// remains valid for as long as this slot is held, making it suitable for
+    /// aliasing-sensitive uses such as FFI callbacks
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.slab.slots[self.idx].get().cast()
+    }
+
     pub fn take(self) -> T {
         let value = unsafe {
             mem::replace(&mut *self.slab.slots[self.idx].get(), MaybeUninit::uninit()).assume_init()
@@ -114,6 +353,48 @@ impl<T> Slot<'_, T> {
         value
     }
 
+    /// Takes the value, leaving [`Default::default`] in its place
+    ///
+    /// The index stays occupied throughout, so a slot can serve as a reusable
+    /// mailbox: the owner drains it each cycle without the index ever being
+    /// released and claimed by another thread in between
+    pub fn take_default(&mut self) -> T
+    where
+        T: Default,
+    {
+        mem::take(&mut **self)
+    }
+
+    /// Whether the value equals [`Default::default`], such as an empty
+    /// mailbox drained by [`Slot::take_default`]
+    pub fn is_default(&self) -> bool
+    where
+        T: Default + PartialEq,
+    {
+        (**self).eq(&T::default())
+    }
+
+    /// Takes the value alongside the index it occupied, which is free for
+    /// reuse once this returns
+    pub fn take_with_index(self) -> (usize, T) {
+        let idx = self.idx;
+        (idx, self.take())
+    }
+
+    /// Moves the value into a [`Boxed64`](crate::boxed::Boxed64) slot whose
+    /// lifetime isn't tied to [`Fixed64`], releasing this index
+    ///
+    /// This allocates a new slab of 64 cells to hold the single value, which
+    /// is deallocated once the returned slot drops
+    #[cfg(feature = "extern_crate_alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extern_crate_alloc")))]
+    pub fn to_boxed(self) -> crate::boxed::Slot<T> {
+        crate::boxed::Boxed64::new()
+            .get_uninit_slot()
+            .unwrap()
+            .insert(self.take())
+    }
+
     /// Reconstruct [`Slot`] from a tagged pointer to become the borrow-owner of
     /// a [`Fixed64`] cell until dropped
     ///
@@ -122,9 +403,21 @@ impl<T> Slot<'_, T> {
     /// It must be guaranteed that the underlying [`Fixed64`] be valid and at
     /// the same address for the lifetime of [`Slot`].
     pub unsafe fn from_raw(ptr: *mut ()) -> Self {
+        Self::from_raw_parts(ptr.map_addr(|addr| addr & IDX_MASK), ptr.addr() & IDX)
+    }
+
+    /// Reconstruct [`Slot`] from the slab pointer and index returned by
+    /// [`Slot::into_raw_parts`] to become the borrow-owner of a [`Fixed64`]
+    /// cell until dropped
+    ///
+    /// # Safety
+    ///
+    /// It must be guaranteed that the underlying [`Fixed64`] be valid and at
+    /// the same address for the lifetime of [`Slot`].
+    pub unsafe fn from_raw_parts(slab: *mut (), idx: usize) -> Self {
         Self {
-            slab: &*(ptr.map_addr(|addr| addr & IDX_MASK) as *const _),
-            idx: ptr as usize & IDX,
+            slab: &*(slab as *const Fixed64<T>),
+            idx,
         }
     }
 
@@ -136,15 +429,61 @@ impl<T> Slot<'_, T> {
     /// For drop to be called on the interior value this must be converted back
     /// into [`Slot`] prior to [`Fixed64`] being dropped
     pub fn into_raw(self) -> *mut () {
+        let (slab, idx) = self.into_raw_parts();
+
+        slab.map_addr(|addr| addr | idx)
+    }
+
+    /// Consumes [`Slot`], returning the untagged pointer to the underlying
+    /// [`Fixed64`] alongside the index
+    ///
+    /// Unlike [`Slot::into_raw`], the index isn't stored in the low bits of
+    /// the pointer, leaving callers free to pack both however they see fit
+    ///
+    /// # Safety
+    ///
+    /// For drop to be called on the interior value this must be converted back
+    /// into [`Slot`] prior to [`Fixed64`] being dropped
+    pub fn into_raw_parts(self) -> (*mut (), usize) {
         let slot = ManuallyDrop::new(self);
 
-        addr_of!(*slot.slab).map_addr(|addr| addr | slot.idx) as *mut ()
+        (addr_of!(*slot.slab) as *mut (), slot.idx)
     }
 }
 
 unsafe impl<T> Send for Slot<'_, T> where T: Send {}
 unsafe impl<T> Sync for Slot<'_, T> where T: Sync {}
 
+impl<T> Slot<'_, Option<T>> {
+    /// Takes the value out if present, leaving [`None`] while retaining the
+    /// index
+    ///
+    /// The slab tracks ownership of the index and the [`Option`] tracks the
+    /// presence of a value, forming a mailbox. Only `&mut` variants are
+    /// offered as the [`Option`] itself is unsynchronized; to fill and drain
+    /// from multiple threads, hand the slot between them, or hold a
+    /// synchronized value such as a `Mutex<Option<T>>` instead
+    pub fn transpose_take(&mut self) -> Option<T> {
+        (**self).take()
+    }
+
+    /// Inserts `value`, dropping any previous value, and returns a mutable
+    /// reference to it as with [`Option::insert`]
+    pub fn insert_value(&mut self, value: T) -> &mut T {
+        (**self).insert(value)
+    }
+
+    /// Whether no value is present
+    pub fn is_vacant(&self) -> bool {
+        (**self).is_none()
+    }
+
+    /// Whether a value is present
+    pub fn is_occupied(&self) -> bool {
+        (**self).is_some()
+    }
+}
+
 impl<T> Deref for Slot<'_, T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
@@ -161,6 +500,9 @@ impl<T> DerefMut for Slot<'_, T> {
 impl<T> Drop for Slot<'_, T> {
     fn drop(&mut self) {
         unsafe { (*self.slab.slots[self.idx].get()).assume_init_drop() }
+
+        // Release publishes the drop to whoever claims the index next. Fixed64
+        // outlives its slots, so no acquire is needed to free it afterwards
         self.slab
             .occupancy
             .fetch_and(!(1 << self.idx), Ordering::Release);
@@ -185,8 +527,53 @@ where
     }
 }
 
+impl<T> PartialEq<&T> for Slot<'_, T>
+where
+    T: PartialEq<T>,
+{
+    fn eq(&self, other: &&T) -> bool {
+        self.deref().eq(*other)
+    }
+}
+
 impl<T> Eq for Slot<'_, T> where T: PartialEq<T> {}
 
+impl<T> PartialOrd<T> for Slot<'_, T>
+where
+    T: PartialOrd<T>,
+{
+    fn partial_cmp(&self, other: &T) -> Option<cmp::Ordering> {
+        self.deref().partial_cmp(other)
+    }
+}
+
+impl<T> PartialOrd<&T> for Slot<'_, T>
+where
+    T: PartialOrd<T>,
+{
+    fn partial_cmp(&self, other: &&T) -> Option<cmp::Ordering> {
+        self.deref().partial_cmp(*other)
+    }
+}
+
+impl<T> PartialOrd<Slot<'_, T>> for Slot<'_, T>
+where
+    T: PartialOrd<T>,
+{
+    fn partial_cmp(&self, other: &Slot<T>) -> Option<cmp::Ordering> {
+        self.deref().partial_cmp(other)
+    }
+}
+
+impl<T> Ord for Slot<'_, T>
+where
+    T: Ord,
+{
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.deref().cmp(other)
+    }
+}
+
 impl<T> Debug for Slot<'_, T>
 where
     T: Debug,
@@ -196,12 +583,35 @@ where
     }
 }
 
+impl<T> Display for Slot<'_, T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<T> Error for Slot<'_, T>
+where
+    T: Error,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.deref().source()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use alloc::vec::Vec;
-    use core::sync::atomic::Ordering;
-
-    use super::{Fixed64, Slot};
+    use alloc::{string::ToString, vec::Vec};
+    use core::{
+        error::Error,
+        fmt::{self, Display},
+        panic::AssertUnwindSafe,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::{Fixed64, Slot, Table64};
     use crate::heapless::UninitSlot;
 
     #[test]
@@ -253,4 +663,330 @@ mod tests {
 
         assert_eq!(slab.occupancy.load(Ordering::Acquire), 0);
     }
+
+    #[test]
+    fn insert_preserves_index() {
+        let slab = Fixed64::new();
+
+        let reserved: Vec<UninitSlot<usize>> =
+            (0..64).filter_map(|_| slab.get_uninit_slot()).collect();
+
+        for (i, uninit_slot) in reserved.into_iter().enumerate() {
+            assert_eq!(uninit_slot.index(), i);
+
+            let slot = uninit_slot.insert(i);
+
+            assert_eq!(slot.index(), i);
+            assert_eq!(slot, i);
+        }
+    }
+
+    #[test]
+    fn iterates_free_indices() {
+        let slab = Fixed64::new();
+
+        let slots: Vec<UninitSlot<usize>> =
+            (0..64).filter_map(|_| slab.get_uninit_slot()).collect();
+
+        assert_eq!(slab.free_indices().count(), 0);
+
+        let slots: Vec<UninitSlot<usize>> = slots
+            .into_iter()
+            .filter(|slot| slot.index() % 3 != 0)
+            .collect();
+
+        assert!(slab.free_indices().eq((0..64).filter(|idx| idx % 3 == 0)));
+
+        drop(slots);
+
+        assert!(slab.free_indices().eq(0..64));
+    }
+
+    #[test]
+    fn converts_into_and_from_raw_parts() {
+        let slab = Fixed64::new();
+
+        let slots: Vec<Slot<usize>> = (0..64)
+            .map(|i| slab.get_uninit_slot().unwrap().insert(i))
+            .collect();
+
+        let parts: Vec<(*mut (), usize)> = slots
+            .into_iter()
+            .map(|slot| slot.into_raw_parts())
+            .collect();
+
+        assert!(parts.iter().map(|(_, idx)| *idx).eq(0..64));
+
+        // Parts and tagged pointers are interchangeable
+        let pointers: Vec<*mut ()> = parts
+            .into_iter()
+            .map(|(slab, idx)| unsafe { Slot::<usize>::from_raw_parts(slab, idx) }.into_raw())
+            .collect();
+
+        let slots: Vec<Slot<usize>> = pointers
+            .into_iter()
+            .map(|ptr| {
+                let (slab, idx) = unsafe { Slot::<usize>::from_raw(ptr) }.into_raw_parts();
+                unsafe { Slot::from_raw_parts(slab, idx) }
+            })
+            .collect();
+
+        assert_eq!(slots, (0..64).collect::<Vec<usize>>());
+        assert_eq!(slots[63].index(), 63);
+    }
+
+    #[test]
+    fn filled_is_const_and_fully_occupied() {
+        static TABLE: Table64<u8> = Fixed64::filled(7);
+
+        assert!(TABLE.slab.get_uninit_slot().is_none());
+        assert_eq!(*TABLE, [7; 64]);
+    }
+
+    #[test]
+    fn from_array_is_const_and_fully_occupied() {
+        static SQUARES: Table64<u32> = {
+            let mut squares = [0; 64];
+            let mut idx = 0;
+
+            while idx < 64 {
+                squares[idx] = (idx * idx) as u32;
+                idx += 1;
+            }
+
+            Fixed64::from_array(squares)
+        };
+
+        assert!(SQUARES.slab.get_uninit_slot().is_none());
+        assert_eq!(SQUARES[9], 81);
+        assert!(SQUARES
+            .iter()
+            .enumerate()
+            .all(|(idx, square)| square.eq(&((idx * idx) as u32))));
+    }
+
+    #[test]
+    fn reads_static_table_without_unsafe() {
+        struct Opcode {
+            name: &'static str,
+            operands: u8,
+        }
+
+        static OPCODES: Table64<Opcode> = {
+            let mut opcodes = [const {
+                Opcode {
+                    name: "nop",
+                    operands: 0,
+                }
+            }; 64];
+
+            opcodes[1] = Opcode {
+                name: "push",
+                operands: 1,
+            };
+            opcodes[2] = Opcode {
+                name: "add",
+                operands: 2,
+            };
+
+            Fixed64::from_array(opcodes)
+        };
+
+        assert_eq!(OPCODES[1].name, "push");
+        assert_eq!(OPCODES[2].operands, 2);
+        assert_eq!(OPCODES.iter().filter(|op| op.name.eq("nop")).count(), 62);
+    }
+
+    #[test]
+    fn claims_boundary_indexes() {
+        let slab = Fixed64::<usize>::new();
+
+        for (occupancy, idx) in [(u64::MAX >> 1, 63), (u64::MAX << 1, 0)] {
+            slab.occupancy.store(occupancy, Ordering::Relaxed);
+
+            let uninit_slot = slab.get_uninit_slot().unwrap();
+            assert_eq!(uninit_slot.index(), idx);
+            assert!(slab.get_uninit_slot().is_none());
+        }
+    }
+
+    #[cfg(feature = "extern_crate_alloc")]
+    #[test]
+    fn moves_into_boxed_slot() {
+        let boxed = {
+            let slab = Fixed64::new();
+            let slot = slab.get_uninit_slot().unwrap().insert("arena".to_string());
+
+            let boxed = slot.to_boxed();
+            assert!(slab.free_indices().eq(0..64));
+            boxed
+        };
+
+        assert_eq!(boxed, "arena".to_string());
+    }
+
+    #[test]
+    fn insert_never_drops_uninit_contents() {
+        struct DropCounter<'a>(&'a AtomicUsize);
+
+        impl Drop for DropCounter<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let drops = AtomicUsize::new(0);
+        let slab = Fixed64::new();
+
+        let slots: Vec<Slot<DropCounter>> = (0..64)
+            .map(|_| slab.get_uninit_slot().unwrap().insert(DropCounter(&drops)))
+            .collect();
+
+        assert_eq!(drops.load(Ordering::Relaxed), 0);
+
+        drop(slots);
+
+        assert_eq!(drops.load(Ordering::Relaxed), 64);
+
+        let slot = slab.get_uninit_slot().unwrap().insert(DropCounter(&drops));
+
+        assert_eq!(drops.load(Ordering::Relaxed), 64);
+
+        drop(slot);
+
+        assert_eq!(drops.load(Ordering::Relaxed), 65);
+    }
+
+    unsafe fn write_through(ptr: *mut usize, value: usize) {
+        ptr.write(value);
+    }
+
+    #[test]
+    fn writes_through_raw_pointers() {
+        let slab = Fixed64::new();
+
+        let mut uninit_slot = slab.get_uninit_slot().unwrap();
+        let ptr = uninit_slot.as_mut_ptr();
+
+        let mut slot = uninit_slot.insert(1);
+
+        assert_eq!(slot.as_ptr(), ptr as *const usize);
+
+        unsafe { write_through(slot.as_mut_ptr(), 2) };
+        assert_eq!(slot, 2);
+
+        let shared = &slot;
+        unsafe { write_through(shared.as_ptr() as *mut usize, 3) };
+        assert_eq!(slot, 3);
+    }
+
+    #[derive(Debug)]
+    struct RootCause;
+
+    impl Display for RootCause {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("root cause")
+        }
+    }
+
+    impl Error for RootCause {}
+
+    #[derive(Debug)]
+    struct Failure(RootCause);
+
+    impl Display for Failure {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("failure")
+        }
+    }
+
+    impl Error for Failure {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn slot_forwards_error_source() {
+        let slab = Fixed64::new();
+
+        let slot = slab.get_uninit_slot().unwrap().insert(Failure(RootCause));
+        let err: &dyn Error = &slot;
+
+        assert_eq!(err.to_string(), "failure");
+
+        let source = err.source().unwrap();
+
+        assert!(source.is::<RootCause>());
+        assert_eq!(source.to_string(), "root cause");
+        assert!(source.source().is_none());
+    }
+
+    #[test]
+    fn insert_with_releases_on_panic() {
+        let slab = Fixed64::<usize>::new();
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            slab.get_uninit_slot()
+                .unwrap()
+                .insert_with(|| panic!("constructor failed"))
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(slab.occupancy.load(Ordering::Acquire), 0);
+
+        let slot = slab.get_uninit_slot().unwrap().insert_with(|| 1);
+
+        assert_eq!(slot.index(), 0);
+        assert_eq!(*slot, 1);
+    }
+
+    #[test]
+    fn reports_index_and_slab_addr() {
+        let slab = Fixed64::new();
+
+        let slots: Vec<_> = (0..64)
+            .map(|_| {
+                let uninit = slab.get_uninit_slot().unwrap();
+                let (idx, addr) = (uninit.index(), uninit.slab_addr());
+
+                assert_eq!(addr, &slab as *const _ as usize);
+
+                let slot = uninit.insert(idx);
+                assert_eq!((slot.index(), slot.slab_addr()), (idx, addr));
+
+                slot
+            })
+            .collect();
+
+        assert!(slots.iter().enumerate().all(|(idx, slot)| slot.eq(&idx)));
+    }
+
+    #[test]
+    fn try_insert_with_retains_reservation() {
+        let slab = Fixed64::new();
+
+        let (uninit, ()) = slab
+            .get_uninit_slot()
+            .unwrap()
+            .try_insert_with(|| Err(()))
+            .unwrap_err();
+
+        assert_eq!(uninit.index(), 0);
+        assert_eq!(slab.get_uninit_slot().unwrap().index(), 1);
+
+        let slot = uninit.try_insert_with(|| Ok::<_, ()>(1)).ok().unwrap();
+
+        assert_eq!((slot.index(), *slot), (0, 1));
+
+        let (uninit, ()) = slab
+            .get_uninit_slot()
+            .unwrap()
+            .try_insert_with(|| Err(()))
+            .unwrap_err();
+
+        drop(uninit);
+
+        assert_eq!(slab.occupancy.load(Ordering::Acquire), 1);
+    }
 }
@@ -0,0 +1,287 @@
+use std::{
+    io::{self, BufRead, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write},
+    string::String,
+    vec::Vec,
+};
+
+use crate::{boxed, heapless};
+
+impl<T> Read for boxed::Slot<T>
+where
+    T: Read,
+{
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (**self).read(buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (**self).read_vectored(bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        (**self).read_to_end(buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        (**self).read_to_string(buf)
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        (**self).read_exact(buf)
+    }
+}
+
+impl<T> Write for boxed::Slot<T>
+where
+    T: Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (**self).write(buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (**self).write_vectored(bufs)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        (**self).flush()
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        (**self).write_all(buf)
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, fmt: core::fmt::Arguments<'_>) -> io::Result<()> {
+        (**self).write_fmt(fmt)
+    }
+}
+
+impl<T> Seek for boxed::Slot<T>
+where
+    T: Seek,
+{
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        (**self).seek(pos)
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> io::Result<u64> {
+        (**self).stream_position()
+    }
+}
+
+impl<T> BufRead for boxed::Slot<T>
+where
+    T: BufRead,
+{
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        (**self).fill_buf()
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        (**self).consume(amt)
+    }
+
+    #[inline]
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> io::Result<usize> {
+        (**self).read_until(byte, buf)
+    }
+
+    #[inline]
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        (**self).read_line(buf)
+    }
+}
+
+impl<T> Read for heapless::Slot<'_, T>
+where
+    T: Read,
+{
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (**self).read(buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (**self).read_vectored(bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        (**self).read_to_end(buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+        (**self).read_to_string(buf)
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        (**self).read_exact(buf)
+    }
+}
+
+impl<T> Write for heapless::Slot<'_, T>
+where
+    T: Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (**self).write(buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (**self).write_vectored(bufs)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        (**self).flush()
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        (**self).write_all(buf)
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, fmt: core::fmt::Arguments<'_>) -> io::Result<()> {
+        (**self).write_fmt(fmt)
+    }
+}
+
+impl<T> Seek for heapless::Slot<'_, T>
+where
+    T: Seek,
+{
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        (**self).seek(pos)
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> io::Result<u64> {
+        (**self).stream_position()
+    }
+}
+
+impl<T> BufRead for heapless::Slot<'_, T>
+where
+    T: BufRead,
+{
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        (**self).fill_buf()
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        (**self).consume(amt)
+    }
+
+    #[inline]
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> io::Result<usize> {
+        (**self).read_until(byte, buf)
+    }
+
+    #[inline]
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        (**self).read_line(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{self, BufRead, Cursor, Read, Seek, SeekFrom, Write},
+        string::String,
+        vec::Vec,
+    };
+
+    use crate::{boxed::Boxed64, heapless::Fixed64};
+
+    #[test]
+    fn boxed_slot_copies_both_directions() -> io::Result<()> {
+        let slab = Boxed64::new();
+
+        let mut writer = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert(Cursor::new(Vec::new()));
+
+        let mut reader = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert(Cursor::new(b"hello\nworld\n".to_vec()));
+
+        assert_eq!(io::copy(&mut reader, &mut writer)?, 12);
+        assert_eq!(writer.get_ref(), b"hello\nworld\n");
+
+        writer.rewind()?;
+
+        let mut line = String::new();
+        writer.read_line(&mut line)?;
+        assert_eq!(line, "hello\n");
+
+        writer.seek(SeekFrom::End(0))?;
+        write!(writer, "{}!", 42)?;
+        writer.flush()?;
+
+        writer.seek(SeekFrom::Start(6))?;
+
+        let mut rest = String::new();
+        writer.read_to_string(&mut rest)?;
+        assert_eq!(rest, "world\n42!");
+
+        Ok(())
+    }
+
+    #[test]
+    fn heapless_slot_copies_both_directions() -> io::Result<()> {
+        let slab = Fixed64::new();
+
+        let mut writer = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert(Cursor::new(Vec::new()));
+
+        let mut reader = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert(Cursor::new(b"hello\nworld\n".to_vec()));
+
+        assert_eq!(io::copy(&mut reader, &mut writer)?, 12);
+        assert_eq!(writer.get_ref(), b"hello\nworld\n");
+
+        writer.rewind()?;
+
+        let mut lines = Vec::new();
+        for line in (&mut writer).lines() {
+            lines.push(line?);
+        }
+        assert_eq!(lines, ["hello", "world"]);
+
+        let mut buf = [0; 5];
+        writer.seek(SeekFrom::Start(6))?;
+        writer.read_exact(&mut buf)?;
+        assert_eq!(&buf, b"world");
+
+        Ok(())
+    }
+}
@@ -4,13 +4,40 @@
 #[cfg(any(test, feature = "extern_crate_alloc"))]
 extern crate alloc;
 
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
 pub(crate) const IDX: usize = (1 << 6) - 1;
 pub(crate) const IDX_MASK: usize = !IDX;
 
+/// Iterate over the clear bits of an occupancy word from lowest to highest
+pub(crate) fn free_indices(occupancy: u64) -> impl Iterator<Item = usize> {
+    let mut free = !occupancy;
+
+    core::iter::from_fn(move || {
+        if free.eq(&0) {
+            None
+        } else {
+            let idx = free.trailing_zeros() as usize;
+
+            // Clear lowest set bit
+            free &= free - 1;
+
+            Some(idx)
+        }
+    })
+}
+
 #[cfg_attr(docsrs, doc(cfg(feature = "extern_crate_alloc")))]
 #[cfg(feature = "extern_crate_alloc")]
 pub mod arena;
+pub mod backoff;
 #[cfg_attr(docsrs, doc(cfg(feature = "extern_crate_alloc")))]
 #[cfg(feature = "extern_crate_alloc")]
 pub mod boxed;
 pub mod heapless;
+#[cfg(feature = "std")]
+mod io;
+#[cfg_attr(docsrs, doc(cfg(feature = "shared")))]
+#[cfg(feature = "shared")]
+pub mod shared;
@@ -0,0 +1,610 @@
+use core::{
+    error::Error,
+    fmt::{Debug, Display},
+    mem::ManuallyDrop,
+    ops::Deref,
+    sync::atomic::{fence, AtomicU32, Ordering},
+};
+
+use crate::boxed::{Inner, Slot};
+
+/// Low bits of a slot's state word counting [`SharedSlot`] handles
+pub(crate) const REFS: u32 = (1 << 8) - 1;
+/// Increment of the generation stored in the high bits of a slot's state word
+pub(crate) const GENERATION: u32 = 1 << 8;
+
+impl<T> Slot<T> {
+    /// Converts into a [`SharedSlot`] so that the value can be shared by
+    /// multiple read-only handles
+    pub fn into_shared(self) -> SharedSlot<T> {
+        let slot = ManuallyDrop::new(self);
+
+        // Exclusively owned slots always have a reference count of zero. Release
+        // publishes the value to upgrading weak handles
+        unsafe { &*slot.slab() }.state[slot.idx].fetch_add(1, Ordering::Release);
+
+        SharedSlot {
+            slab: slot.slab(),
+            idx: slot.idx,
+        }
+    }
+
+    /// Converts into a [`FrozenSlot`], permanently giving up mutable access
+    pub fn freeze(self) -> FrozenSlot<T> {
+        FrozenSlot {
+            shared: self.into_shared(),
+        }
+    }
+
+    /// Creates a [`WeakSlot`] observing the value held at this index
+    ///
+    /// While this [`Slot`] remains exclusive, upgrading will fail; upgrades
+    /// only succeed after converting into a [`SharedSlot`]
+    pub fn downgrade(&self) -> WeakSlot<T> {
+        unsafe { WeakSlot::new(self.slab(), self.idx) }
+    }
+}
+
+/// Provides shared read-only access over an index of
+/// [`Boxed64`](crate::boxed::Boxed64). The value is dropped and the index
+/// released once the last clone has dropped
+///
+/// The reference count is stored within the slab, limiting each value to 255
+/// handles; cloning beyond this panics
+pub struct SharedSlot<T> {
+    slab: *const Inner<T>,
+    idx: usize,
+}
+
+impl<T> SharedSlot<T> {
+    fn inner(&self) -> &Inner<T> {
+        unsafe { &*self.slab }
+    }
+
+    fn state(&self) -> &AtomicU32 {
+        &self.inner().state[self.idx]
+    }
+
+    /// The index of this slot within [`Boxed64`](crate::boxed::Boxed64)
+    pub fn index(this: &Self) -> usize {
+        this.idx
+    }
+
+    /// The number of handles sharing this value
+    pub fn ref_count(this: &Self) -> usize {
+        (this.state().load(Ordering::Acquire) & REFS) as usize
+    }
+
+    /// Creates a [`WeakSlot`] observing this value without keeping it alive
+    pub fn downgrade(this: &Self) -> WeakSlot<T> {
+        unsafe { WeakSlot::new(this.slab, this.idx) }
+    }
+
+    /// Returns a mutable reference to the value if this is the only handle and
+    /// it cannot be upgraded to, as with `Arc::get_mut`
+    ///
+    /// Weak handles are counted per slab rather than per index, so this
+    /// conservatively returns [`None`] while any [`WeakSlot`] into the same
+    /// slab is alive
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        // Acquire synchronizes with the release of every other dropped handle
+        if (this.state().load(Ordering::Acquire) & REFS).ne(&1)
+            || this.inner().weak.load(Ordering::Acquire).ne(&1)
+        {
+            return None;
+        }
+
+        Some(unsafe { (*this.inner().slots[this.idx].get()).assume_init_mut() })
+    }
+
+    /// Returns the value if this is the only handle, otherwise returning this
+    /// handle back
+    pub fn try_unwrap(this: Self) -> Result<T, Self> {
+        Self::try_into_slot(this).map(Slot::take)
+    }
+
+    /// Recovers exclusive ownership as a [`Slot`] if this is the only handle,
+    /// otherwise returning this handle back
+    pub fn try_into_slot(this: Self) -> Result<Slot<T>, Self> {
+        if this
+            .state()
+            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |state| {
+                (state & REFS).eq(&1).then_some(state - 1)
+            })
+            .is_err()
+        {
+            return Err(this);
+        }
+
+        let this = ManuallyDrop::new(this);
+
+        Ok(unsafe { Slot::new(this.slab, this.idx) })
+    }
+}
+
+unsafe impl<T> Send for SharedSlot<T> where T: Send + Sync {}
+unsafe impl<T> Sync for SharedSlot<T> where T: Send + Sync {}
+
+impl<T> Clone for SharedSlot<T> {
+    fn clone(&self) -> Self {
+        if self
+            .state()
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |state| {
+                (state & REFS).ne(&REFS).then_some(state + 1)
+            })
+            .is_err()
+        {
+            panic!("SharedSlot reference count overflow");
+        }
+
+        SharedSlot {
+            slab: self.slab,
+            idx: self.idx,
+        }
+    }
+}
+
+impl<T> Deref for SharedSlot<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        unsafe { (*self.inner().slots[self.idx].get()).assume_init_ref() }
+    }
+}
+
+impl<T> Drop for SharedSlot<T> {
+    fn drop(&mut self) {
+        if (self.state().fetch_sub(1, Ordering::Release) & REFS).ne(&1) {
+            return;
+        }
+
+        // Synchronize with the release of every other handle before dropping
+        fence(Ordering::Acquire);
+
+        unsafe {
+            (*self.inner().slots[self.idx].get()).assume_init_drop();
+            Inner::release(self.slab, self.idx);
+        }
+    }
+}
+
+impl<T> PartialEq<T> for SharedSlot<T>
+where
+    T: PartialEq<T>,
+{
+    fn eq(&self, other: &T) -> bool {
+        self.deref().eq(other)
+    }
+}
+
+impl<T> Debug for SharedSlot<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<T> Display for SharedSlot<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<T> Error for SharedSlot<T>
+where
+    T: Error,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.deref().source()
+    }
+}
+
+/// Provides immutable shared access over an index of
+/// [`Boxed64`](crate::boxed::Boxed64), created by [`Slot::freeze`]
+///
+/// Unlike [`SharedSlot`], a frozen value can never be recovered as a
+/// [`Slot`] or moved back out, but as the last clone drops the value on
+/// whichever thread holds it, both [`Send`] and [`Sync`] still require
+/// `T: Send + Sync`. Clones share the reference count of [`SharedSlot`] and
+/// are likewise limited to 255 handles
+pub struct FrozenSlot<T> {
+    shared: SharedSlot<T>,
+}
+
+impl<T> FrozenSlot<T> {
+    /// The index of this slot within [`Boxed64`](crate::boxed::Boxed64)
+    pub fn index(this: &Self) -> usize {
+        SharedSlot::index(&this.shared)
+    }
+
+    /// The number of handles sharing this value
+    pub fn ref_count(this: &Self) -> usize {
+        SharedSlot::ref_count(&this.shared)
+    }
+}
+
+impl<T> From<Slot<T>> for FrozenSlot<T> {
+    fn from(slot: Slot<T>) -> Self {
+        slot.freeze()
+    }
+}
+
+impl<T> From<SharedSlot<T>> for FrozenSlot<T> {
+    fn from(shared: SharedSlot<T>) -> Self {
+        FrozenSlot { shared }
+    }
+}
+
+impl<T> Clone for FrozenSlot<T> {
+    fn clone(&self) -> Self {
+        FrozenSlot {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Deref for FrozenSlot<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.shared
+    }
+}
+
+impl<T> PartialEq<T> for FrozenSlot<T>
+where
+    T: PartialEq<T>,
+{
+    fn eq(&self, other: &T) -> bool {
+        self.deref().eq(other)
+    }
+}
+
+impl<T> Debug for FrozenSlot<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl<T> Display for FrozenSlot<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+/// A handle observing the value held at an index of
+/// [`Boxed64`](crate::boxed::Boxed64) without keeping it alive, created by
+/// [`Slot::downgrade`] or [`SharedSlot::downgrade`]
+///
+/// Each weak handle pins the slab allocation, so upgrading never dereferences
+/// freed memory; the slab is deallocated only once its slots and weak handles
+/// have all dropped. Every release of an index advances a 24-bit generation
+/// stored alongside the reference count, which invalidates weak handles to
+/// values previously held at that index. The generation wraps after
+/// 16,777,216 releases of the same index, after which a lingering weak handle
+/// could observe an unrelated value. This bookkeeping costs an extra atomic
+/// increment per release when the `shared` feature is enabled
+pub struct WeakSlot<T> {
+    slab: *const Inner<T>,
+    idx: usize,
+    generation: u32,
+}
+
+impl<T> WeakSlot<T> {
+    /// # Safety
+    ///
+    /// The index must be held by a live [`Slot`] or [`SharedSlot`]
+    unsafe fn new(slab: *const Inner<T>, idx: usize) -> Self {
+        (*slab).weak.fetch_add(1, Ordering::Relaxed);
+
+        WeakSlot {
+            slab,
+            idx,
+            generation: (*slab).state[idx].load(Ordering::Relaxed) & !REFS,
+        }
+    }
+
+    fn inner(&self) -> &Inner<T> {
+        unsafe { &*self.slab }
+    }
+
+    /// The index of this slot within [`Boxed64`](crate::boxed::Boxed64)
+    pub fn index(&self) -> usize {
+        self.idx
+    }
+
+    /// Attempts to obtain a [`SharedSlot`] to the observed value, returning
+    /// [`None`] if the value has since been dropped or is exclusively owned
+    pub fn upgrade(&self) -> Option<SharedSlot<T>> {
+        self.inner().state[self.idx]
+            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |state| {
+                let refs = state & REFS;
+
+                if (state & !REFS).ne(&self.generation) || refs.eq(&0) {
+                    None
+                } else if refs.eq(&REFS) {
+                    panic!("SharedSlot reference count overflow");
+                } else {
+                    Some(state + 1)
+                }
+            })
+            .ok()?;
+
+        Some(SharedSlot {
+            slab: self.slab,
+            idx: self.idx,
+        })
+    }
+}
+
+unsafe impl<T> Send for WeakSlot<T> where T: Send + Sync {}
+unsafe impl<T> Sync for WeakSlot<T> where T: Send + Sync {}
+
+impl<T> Clone for WeakSlot<T> {
+    fn clone(&self) -> Self {
+        self.inner().weak.fetch_add(1, Ordering::Relaxed);
+
+        WeakSlot {
+            slab: self.slab,
+            idx: self.idx,
+            generation: self.generation,
+        }
+    }
+}
+
+impl<T> Debug for WeakSlot<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("(WeakSlot)")
+    }
+}
+
+impl<T> Drop for WeakSlot<T> {
+    fn drop(&mut self) {
+        unsafe { Inner::dealloc(self.slab) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{sync::Arc, vec::Vec};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::{FrozenSlot, SharedSlot, WeakSlot};
+    use crate::boxed::Boxed64;
+
+    struct DropCounter(Arc<AtomicUsize>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn clones_across_threads() {
+        let slab = Boxed64::new();
+
+        let shared = slab.get_uninit_slot().unwrap().insert(42).into_shared();
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                let shared = shared.clone();
+
+                scope.spawn(move || {
+                    for _ in 0..1000 {
+                        assert_eq!(*shared.clone(), 42);
+                    }
+                });
+            }
+        });
+
+        assert_eq!(SharedSlot::ref_count(&shared), 1);
+        assert_eq!(SharedSlot::try_unwrap(shared).ok(), Some(42));
+        assert_eq!(slab.free_indices().count(), 64);
+    }
+
+    #[test]
+    fn frozen_reads_across_threads() {
+        let slab = Boxed64::new();
+        let drops = Arc::new(AtomicUsize::new(0));
+
+        let frozen = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert((42, DropCounter(drops.clone())))
+            .freeze();
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                let frozen = frozen.clone();
+
+                scope.spawn(move || {
+                    for _ in 0..1000 {
+                        assert_eq!(frozen.0, 42);
+                    }
+                });
+            }
+        });
+
+        assert_eq!(FrozenSlot::ref_count(&frozen), 1);
+        assert_eq!(drops.load(Ordering::Relaxed), 0);
+
+        drop(frozen);
+
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+        assert_eq!(slab.free_indices().count(), 64);
+    }
+
+    #[test]
+    fn drops_once_in_every_order() {
+        let orders: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+
+        for order in orders {
+            let drops = Arc::new(AtomicUsize::new(0));
+            let slab = Boxed64::new();
+
+            let shared = slab
+                .get_uninit_slot()
+                .unwrap()
+                .insert(DropCounter(drops.clone()))
+                .into_shared();
+
+            let mut handles: Vec<Option<SharedSlot<DropCounter>>> =
+                alloc::vec![Some(shared.clone()), Some(shared.clone()), Some(shared)];
+
+            for (n, idx) in order.into_iter().enumerate() {
+                assert_eq!(drops.load(Ordering::Relaxed), 0);
+                assert_eq!(slab.free_indices().count(), 63);
+
+                let handle = handles[idx].take().unwrap();
+
+                assert_eq!(SharedSlot::ref_count(&handle), 3 - n);
+
+                drop(handle);
+            }
+
+            assert_eq!(drops.load(Ordering::Relaxed), 1);
+            assert_eq!(slab.free_indices().count(), 64);
+        }
+    }
+
+    #[test]
+    fn last_handle_deallocates_retired_slab() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let slab = Boxed64::new();
+
+        let shared = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert(DropCounter(drops.clone()))
+            .into_shared();
+
+        let clone = shared.clone();
+
+        drop(slab);
+        drop(shared);
+
+        assert_eq!(drops.load(Ordering::Relaxed), 0);
+
+        std::thread::spawn(move || drop(clone)).join().unwrap();
+
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn recovers_exclusive_ownership() {
+        let slab = Boxed64::new();
+
+        let shared = slab.get_uninit_slot().unwrap().insert(1).into_shared();
+        let clone = shared.clone();
+
+        let shared = SharedSlot::try_into_slot(shared).unwrap_err();
+
+        drop(clone);
+
+        let mut slot = SharedSlot::try_into_slot(shared).unwrap();
+        *slot += 1;
+
+        assert_eq!(slot, 2);
+        assert_eq!(slab.free_indices().count(), 63);
+    }
+
+    #[test]
+    fn mutates_only_when_unique() {
+        let slab = Boxed64::new();
+
+        let mut shared = slab.get_uninit_slot().unwrap().insert(1).into_shared();
+        *SharedSlot::get_mut(&mut shared).unwrap() += 1;
+
+        let clone = shared.clone();
+        assert_eq!(SharedSlot::get_mut(&mut shared), None);
+        drop(clone);
+
+        let weak = SharedSlot::downgrade(&shared);
+        assert_eq!(SharedSlot::get_mut(&mut shared), None);
+        drop(weak);
+
+        assert_eq!(SharedSlot::get_mut(&mut shared), Some(&mut 2));
+    }
+
+    #[test]
+    fn upgrades_while_shared() {
+        let slab = Boxed64::new();
+
+        let slot = slab.get_uninit_slot().unwrap().insert(7);
+        let weak = slot.downgrade();
+
+        assert!(weak.upgrade().is_none());
+
+        let shared = slot.into_shared();
+        let upgraded = weak.upgrade().unwrap();
+
+        assert_eq!(upgraded, 7);
+        assert_eq!(SharedSlot::ref_count(&shared), 2);
+
+        drop(shared);
+        drop(upgraded);
+
+        assert!(weak.upgrade().is_none());
+        assert_eq!(slab.free_indices().count(), 64);
+    }
+
+    #[test]
+    fn upgrade_after_reuse_fails() {
+        let slab = Boxed64::new();
+
+        let shared = slab.get_uninit_slot().unwrap().insert(1).into_shared();
+        let idx = SharedSlot::index(&shared);
+        let weak = SharedSlot::downgrade(&shared);
+
+        drop(shared);
+
+        let reused = slab.get_uninit_slot().unwrap().insert(2).into_shared();
+
+        assert_eq!(SharedSlot::index(&reused), idx);
+        assert!(weak.upgrade().is_none());
+        assert_eq!(SharedSlot::ref_count(&reused), 1);
+    }
+
+    #[test]
+    fn weak_slots_pin_freed_slab() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let slab = Boxed64::new();
+
+        let shared = slab
+            .get_uninit_slot()
+            .unwrap()
+            .insert(DropCounter(drops.clone()))
+            .into_shared();
+
+        let weak: Vec<WeakSlot<DropCounter>> =
+            (0..4).map(|_| SharedSlot::downgrade(&shared)).collect();
+
+        drop(slab);
+        drop(shared);
+
+        assert_eq!(drops.load(Ordering::Relaxed), 1);
+
+        std::thread::scope(|scope| {
+            for weak in weak {
+                scope.spawn(move || assert!(weak.upgrade().is_none()));
+            }
+        });
+    }
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "extern_crate_alloc")]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+};
+
+use arena64::boxed::{Boxed64, Slot, UninitSlot};
+
+/// Tracks live 64-byte aligned allocations, which only slabs use
+struct SlabCounter;
+
+static LIVE_SLABS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for SlabCounter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.align().eq(&64) {
+            LIVE_SLABS.fetch_add(1, Ordering::SeqCst);
+        }
+
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if layout.align().eq(&64) {
+            LIVE_SLABS.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: SlabCounter = SlabCounter;
+
+struct DropCounter(Arc<AtomicUsize>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn reserve_insert_and_drop_on_separate_threads() {
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let (reserved_tx, reserved_rx) = mpsc::channel::<UninitSlot<DropCounter>>();
+    let (inserted_tx, inserted_rx) = mpsc::channel::<Slot<DropCounter>>();
+
+    let reserver = thread::spawn(move || {
+        let slab = Boxed64::new();
+
+        reserved_tx.send(slab.get_uninit_slot().unwrap()).unwrap();
+
+        // Retire the slab so that the outstanding slot becomes responsible for freeing
+        // it
+        drop(slab);
+    });
+
+    let inserter = thread::spawn({
+        let drops = drops.clone();
+
+        move || {
+            let uninit_slot = reserved_rx.recv().unwrap();
+
+            inserted_tx
+                .send(uninit_slot.insert(DropCounter(drops)))
+                .unwrap();
+        }
+    });
+
+    let dropper = thread::spawn({
+        let drops = drops.clone();
+
+        move || {
+            let slot = inserted_rx.recv().unwrap();
+
+            assert_eq!(drops.load(Ordering::SeqCst), 0);
+            assert_eq!(LIVE_SLABS.load(Ordering::SeqCst), 1);
+
+            drop(slot);
+        }
+    });
+
+    reserver.join().unwrap();
+    inserter.join().unwrap();
+    dropper.join().unwrap();
+
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+    assert_eq!(LIVE_SLABS.load(Ordering::SeqCst), 0);
+}